@@ -1,12 +1,14 @@
 use web_sys::{wasm_bindgen::UnwrapThrowExt, HtmlElement};
 
 use crate::{console_error, consts::TABSTER_ATTRIBUTE_NAME, types};
+use serde::Serialize;
+use serde_json::Value;
 
 pub fn get_tabster_attribute(props: types::TabsterAttributeProps) -> types::TabsterDOMAttribute {
     (TABSTER_ATTRIBUTE_NAME.to_string(), props.json_string())
 }
 pub fn get_tabster_attribute_plain(props: &types::TabsterAttributeProps) -> String {
-    serde_json::to_string(&props).unwrap_throw()
+    props.clone().json_string()
 }
 
 /// Updates Tabster props object with new props.
@@ -15,11 +17,48 @@ pub fn get_tabster_attribute_plain(props: &types::TabsterAttributeProps) -> Stri
 /// @param newProps new Tabster props to add.
 ///  When the value of a property in newProps is undefined, the property
 ///  will be removed from the attribute.
-pub fn merge_tabster_props(
-    props: &mut types::TabsterAttributeProps,
-    new_props: types::TabsterAttributeProps,
-) {
-    *props = new_props;
+///
+/// The props are merged deeply, the parts and the fields which are not
+/// mentioned in `new_props` are kept. As `None` values of the typed props
+/// are not serialized, the removal is expressed with an explicit `null`
+/// in a `serde_json::Value`, e.g. `json!({ "mover": null })`.
+pub fn merge_tabster_props(props: &mut types::TabsterAttributeProps, new_props: impl Serialize) {
+    let (mut value, new_value) = match (
+        serde_json::to_value(&*props),
+        serde_json::to_value(new_props),
+    ) {
+        (Ok(value), Ok(new_value)) => (value, new_value),
+        (Err(err), _) | (_, Err(err)) => {
+            console_error!("data-tabster attribute error: {}", err.to_string());
+            return;
+        }
+    };
+
+    merge_json(&mut value, new_value);
+
+    match serde_json::from_value(value) {
+        Ok(merged) => *props = merged,
+        Err(err) => {
+            console_error!("data-tabster attribute error: {}", err.to_string());
+        }
+    }
+}
+
+fn merge_json(value: &mut Value, new_value: Value) {
+    match (value, new_value) {
+        (Value::Object(value), Value::Object(new_value)) => {
+            for (key, new_value) in new_value {
+                if new_value.is_null() {
+                    value.shift_remove(&key);
+                } else if let Some(value) = value.get_mut(&key) {
+                    merge_json(value, new_value);
+                } else {
+                    value.insert(key, new_value);
+                }
+            }
+        }
+        (value, new_value) => *value = new_value,
+    }
 }
 
 /// Sets or updates Tabster attribute of the element.
@@ -30,7 +69,7 @@ pub fn merge_tabster_props(
 ///  will be removed from the attribute.
 pub fn set_tabster_attribute(
     element: HtmlElement,
-    new_props: types::TabsterAttributeProps,
+    new_props: impl Serialize,
     update: Option<bool>,
 ) {
     let mut props: Option<types::TabsterAttributeProps> = None;
@@ -66,3 +105,84 @@ pub fn set_tabster_attribute(
             .unwrap_throw();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn props(value: Value) -> types::TabsterAttributeProps {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn merged(value: Value, new_value: Value) -> Value {
+        let mut props = props(value);
+        merge_tabster_props(&mut props, new_value);
+        serde_json::to_value(&props).unwrap()
+    }
+
+    #[test]
+    fn merge_json_is_deep() {
+        let mut value = json!({ "a": { "b": 1, "c": { "d": 2 } }, "e": 3 });
+        merge_json(
+            &mut value,
+            json!({ "a": { "c": { "f": 4 } }, "e": [5], "g": null }),
+        );
+
+        assert_eq!(
+            value,
+            json!({ "a": { "b": 1, "c": { "d": 2, "f": 4 } }, "e": [5] })
+        );
+    }
+
+    #[test]
+    fn merge_json_removes_nulls() {
+        let mut value = json!({ "a": { "b": 1, "c": 2 }, "d": {} });
+        merge_json(&mut value, json!({ "a": { "b": null }, "d": null }));
+
+        assert_eq!(value, json!({ "a": { "c": 2 } }));
+    }
+
+    #[test]
+    fn merges_the_fields_of_a_part() {
+        assert_eq!(
+            merged(
+                json!({ "mover": { "direction": 1, "cyclic": true } }),
+                json!({ "mover": { "cyclic": false, "memorizeCurrent": true } }),
+            ),
+            json!({ "mover": { "direction": 1, "cyclic": false, "memorizeCurrent": true } })
+        );
+    }
+
+    #[test]
+    fn keeps_the_sibling_parts() {
+        assert_eq!(
+            merged(
+                json!({ "root": {}, "mover": { "direction": 1 } }),
+                json!({ "sys": { "dummyInputsPosition": 1 } }),
+            ),
+            json!({
+                "root": {},
+                "mover": { "direction": 1 },
+                "sys": { "dummyInputsPosition": 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn null_removes_a_part_or_a_field() {
+        assert_eq!(
+            merged(
+                json!({ "root": {}, "mover": { "direction": 1, "cyclic": true } }),
+                json!({ "root": null, "mover": { "cyclic": null } }),
+            ),
+            json!({ "mover": { "direction": 1 } })
+        );
+    }
+
+    #[test]
+    fn root_only_props_are_not_empty() {
+        assert!(!props(json!({ "root": {} })).is_empty());
+        assert!(props(json!({})).is_empty());
+    }
+}
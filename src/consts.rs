@@ -13,12 +13,83 @@ pub mod mover_directions {
     pub const GRID_LINEAR: u8 = 4; // Two-dimentional movement depending on the visual placement. Allows linear movement.
 }
 
+pub mod mover_keys {
+    pub const ARROW_UP: u8 = 1;
+    pub const ARROW_DOWN: u8 = 2;
+    pub const ARROW_LEFT: u8 = 3;
+    pub const ARROW_RIGHT: u8 = 4;
+    pub const PAGE_UP: u8 = 5;
+    pub const PAGE_DOWN: u8 = 6;
+    pub const HOME: u8 = 7;
+    pub const END: u8 = 8;
+}
+
+/// `KeyboardEvent.key` values Tabster reacts to.
+pub mod keys {
+    pub const TAB: &str = "Tab";
+    pub const ENTER: &str = "Enter";
+    pub const ESCAPE: &str = "Escape";
+    pub const SPACE: &str = " ";
+    pub const PAGE_UP: &str = "PageUp";
+    pub const PAGE_DOWN: &str = "PageDown";
+    pub const END: &str = "End";
+    pub const HOME: &str = "Home";
+    pub const ARROW_LEFT: &str = "ArrowLeft";
+    pub const ARROW_UP: &str = "ArrowUp";
+    pub const ARROW_RIGHT: &str = "ArrowRight";
+    pub const ARROW_DOWN: &str = "ArrowDown";
+}
+
 pub mod visibilities {
     pub const INVISIBLE: u8 = 0;
     pub const PARTIALLY_VISIBLE: u8 = 1;
     pub const VISIBLE: u8 = 2;
 }
 
+pub mod restore_focus_orders {
+    pub const HISTORY: u8 = 0;
+    pub const DELOSER_DEFAULT: u8 = 1;
+    pub const ROOT_DEFAULT: u8 = 2;
+    pub const DELOSER_FIRST: u8 = 3;
+    pub const ROOT_FIRST: u8 = 4;
+}
+
+/// The upstream naming: the source is where the focus is lost from (the
+/// popup), the target is where it is restored to (the popup trigger).
+pub mod restorer_types {
+    pub const SOURCE: u8 = 0;
+    pub const TARGET: u8 = 1;
+}
+
+pub mod observed_element_accessibilities {
+    pub const ANY: u8 = 0;
+    pub const ACCESSIBLE: u8 = 1;
+    pub const FOCUSABLE: u8 = 2;
+}
+
+pub const RESTORER_RESTORE_FOCUS_EVENT_NAME: &str = "tabster:restorer:restore-focus";
+
+/// Dispatched on the elements of a Mover with `track_state` when their state
+/// changes, the detail is `MoverElementState`.
+pub const MOVER_STATE_EVENT_NAME: &str = "tabster:mover:state";
+/// Dispatch on an element inside a Mover to move the focus like the arrow keys
+/// do, the detail is `MoverMoveFocusEventDetail`.
+pub const MOVER_MOVE_FOCUS_EVENT_NAME: &str = "tabster:mover:movefocus";
+/// Dispatch on a Mover element to set (or reset) its memorized element, the
+/// detail is `{ memorizedElement?: HTMLElement }`.
+pub const MOVER_MEMORIZED_ELEMENT_EVENT_NAME: &str = "tabster:mover:memorized-element";
+/// Dispatch on an element inside a Groupper to enter or escape it, the detail
+/// is `GroupperMoveFocusEventDetail`.
+pub const GROUPPER_MOVE_FOCUS_EVENT_NAME: &str = "tabster:groupper:movefocus";
+/// Dispatched on a Groupper element when it is entered or escaped, the detail
+/// is `GroupperActiveEventDetail`.
+pub const GROUPPER_ACTIVE_EVENT_NAME: &str = "tabster:groupper:active";
+
+pub mod groupper_move_focus_actions {
+    pub const ENTER: u8 = 1;
+    pub const ESCAPE: u8 = 2;
+}
+
 pub enum GroupperTabbabilities {
     Unlimited,
     // The tabbability is limited to the container and explicit Enter is needed to go inside.
@@ -0,0 +1,319 @@
+use web_sys::{
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    HtmlElement,
+};
+
+use crate::{
+    consts::restore_focus_orders,
+    dom_api::DOM,
+    instance::get_tabster_on_element,
+    keyborg::native_focus,
+    root::RootAPI,
+    tabster::TabsterCore,
+    types::{self, GetWindow, DOMAPI},
+    utils::{TabsterPart, WeakHTMLElement},
+    web::set_timeout,
+};
+use std::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+
+/// How many recently focused elements every deloser remembers.
+const HISTORY_DEPTH: usize = 10;
+
+pub type ArcCellDeloser = Arc<RefCell<Deloser>>;
+
+pub struct Deloser {
+    part: TabsterPart<types::DeloserProps>,
+    win: Arc<GetWindow>,
+    /// Recently focused elements inside the deloser (with their ids), most
+    /// recent first.
+    history: Vec<WeakHTMLElement<HtmlElement, String>>,
+}
+
+impl Deref for Deloser {
+    type Target = TabsterPart<types::DeloserProps>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.part
+    }
+}
+
+impl DerefMut for Deloser {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.part
+    }
+}
+
+impl Deloser {
+    pub fn new(
+        tabster: Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+        props: types::DeloserProps,
+    ) -> Arc<RefCell<Self>> {
+        let win = tabster.borrow().get_window.clone();
+
+        Arc::new(RefCell::new(Self {
+            part: TabsterPart::new(tabster, element.clone(), props),
+            win,
+            history: vec![],
+        }))
+    }
+
+    pub fn focused(&mut self, element: HtmlElement) {
+        self.history
+            .retain(|el| el.get().is_some_and(|el| el != element));
+        let id = element.id();
+        self.history
+            .insert(0, WeakHTMLElement::new(self.win.clone(), element, Some(id)));
+        self.history.truncate(HISTORY_DEPTH);
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// The most recently focused element which is still in the deloser and
+    /// focusable. When the element itself is gone, an element with the same id
+    /// (a rerendered copy of it) is accepted unless `no_selector_check` is set.
+    fn find_in_history(&self) -> Option<HtmlElement> {
+        let element = self.get_element()?;
+        let focusable = self.tabster.borrow().focusable.clone().unwrap_throw();
+        let focusable = focusable.borrow();
+        let no_selector_check = self.get_props().no_selector_check.unwrap_or_default();
+
+        self.history.iter().find_map(|el| {
+            let candidate = if let Some(el) = el.get() {
+                el
+            } else if !no_selector_check {
+                let id = el.get_data().unwrap_or_default();
+                if id.is_empty() {
+                    return None;
+                }
+
+                let selector =
+                    format!("[id=\"{}\"]", id.replace('\\', "\\\\").replace('"', "\\\""));
+                element
+                    .query_selector(&selector)
+                    .ok()
+                    .flatten()?
+                    .dyn_into::<HtmlElement>()
+                    .ok()?
+            } else {
+                return None;
+            };
+
+            (element.contains(Some(&candidate))
+                && focusable.is_focusable(&candidate, None, None, None))
+            .then_some(candidate)
+        })
+    }
+}
+
+pub struct DeloserAPI {
+    tabster: Arc<RefCell<TabsterCore>>,
+    win: Arc<GetWindow>,
+    /// Delosers which had focus inside, most recent first.
+    history: Vec<ArcCellDeloser>,
+    last_focused: Option<WeakHTMLElement>,
+    restore_focus_timer: Arc<RefCell<Option<i32>>>,
+}
+
+impl DeloserAPI {
+    pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
+        tabster.borrow_mut().queue_init({
+            let tabster = tabster.clone();
+            move || {
+                let on_focus = {
+                    let tabster = tabster.clone();
+                    move |element: HtmlElement| {
+                        Self::on_focus(&tabster, element);
+                    }
+                };
+
+                if let Some(focused_element) = tabster.borrow_mut().focused_element.as_mut() {
+                    focused_element.subscribe(move |_, element, _| {
+                        if let Some(element) = element {
+                            on_focus(element);
+                        }
+                    });
+                }
+            }
+        });
+
+        Self {
+            tabster,
+            win: get_window,
+            history: vec![],
+            last_focused: None,
+            restore_focus_timer: Default::default(),
+        }
+    }
+
+    pub fn dispose(&mut self) {
+        if let Some(timer) = self.restore_focus_timer.borrow_mut().take() {
+            (self.win)().clear_timeout_with_handle(timer);
+        }
+
+        self.history.clear();
+        self.last_focused = None;
+    }
+
+    pub fn create_deloser(
+        &mut self,
+        element: &HtmlElement,
+        props: types::DeloserProps,
+    ) -> ArcCellDeloser {
+        Deloser::new(self.tabster.clone(), element, props)
+    }
+
+    pub fn dispose_deloser(&mut self, deloser: &ArcCellDeloser) {
+        self.history.retain(|d| !Arc::ptr_eq(d, deloser));
+    }
+
+    fn on_focus(tabster: &Arc<RefCell<TabsterCore>>, element: HtmlElement) {
+        let deloser = Self::get_deloser(tabster, &element);
+
+        let Some(api) = tabster.borrow().deloser.clone() else {
+            return;
+        };
+
+        let mut api = api.borrow_mut();
+        api.last_focused = Some(WeakHTMLElement::new(api.win.clone(), element.clone(), None));
+
+        if let Some(deloser) = deloser {
+            api.history.retain(|d| !Arc::ptr_eq(d, &deloser));
+            api.history.insert(0, deloser.clone());
+            deloser.borrow_mut().focused(element);
+        }
+    }
+
+    /// Called by the mutation observer when some nodes are removed from the DOM.
+    /// If the last focused element was among them, the focus is restored once
+    /// the mutations settle.
+    pub(crate) fn on_nodes_removed(&mut self) {
+        let Some(last_focused) = self.last_focused.as_ref() else {
+            return;
+        };
+
+        if last_focused.get().is_some() || self.restore_focus_timer.borrow().is_some() {
+            return;
+        }
+
+        let restore_focus_timer = self.restore_focus_timer.clone();
+        let tabster = self.tabster.clone();
+
+        let timer = set_timeout(
+            &(self.win)(),
+            move || {
+                *restore_focus_timer.borrow_mut() = None;
+                Self::restore_focus(&tabster);
+            },
+            0,
+        );
+
+        *self.restore_focus_timer.borrow_mut() = Some(timer);
+    }
+
+    fn restore_focus(tabster: &Arc<RefCell<TabsterCore>>) {
+        let Some(api) = tabster.borrow().deloser.clone() else {
+            return;
+        };
+
+        let doc = {
+            let mut api = api.borrow_mut();
+            // The lost element is handled once, whatever the outcome is.
+            api.last_focused = None;
+            (api.win)().document().unwrap_throw()
+        };
+
+        // Somebody else has already taken care of the focus.
+        if let Some(active_element) = DOM::get_active_element(&doc) {
+            if Some(active_element) != doc.body().map(Into::into) {
+                return;
+            }
+        }
+
+        let history = api.borrow().history.clone();
+
+        for deloser in history {
+            if let Some(element) = Self::find_restore_candidate(tabster, &deloser) {
+                native_focus(element);
+                return;
+            }
+        }
+    }
+
+    /// Goes through the restore strategies starting from the one specified by
+    /// the deloser (or its root) props, in `RestoreFocusOrder` order.
+    fn find_restore_candidate(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        deloser: &ArcCellDeloser,
+    ) -> Option<HtmlElement> {
+        let deloser = deloser.borrow();
+        let deloser_element = deloser.get_element().filter(|el| el.is_connected())?;
+        let root = RootAPI::get_root(tabster, deloser_element.clone());
+        let root_element = root.as_ref().and_then(|root| root.borrow().get_element());
+        let restore_focus_order = deloser
+            .get_props()
+            .restore_focus_order
+            .or_else(|| root.and_then(|root| root.borrow().get_props().restore_focus_order))
+            .unwrap_or(restore_focus_orders::HISTORY);
+
+        let focusable = tabster.borrow().focusable.clone().unwrap_throw();
+        let find_default = |container: Option<HtmlElement>| {
+            focusable.borrow().find_default(
+                types::FindDefaultProps {
+                    container: container?,
+                    modalizer_id: None,
+                    include_programmatically_focusable: None,
+                    use_active_modalizer: Some(true),
+                    ignore_accessibility: None,
+                },
+                &mut Default::default(),
+            )
+        };
+        let find_first = |container: Option<HtmlElement>| {
+            focusable.borrow().find_first(
+                types::FindFirstProps {
+                    container: container?,
+                    ignore_accessibility: None,
+                    use_active_modalizer: Some(true),
+                },
+                &mut Default::default(),
+            )
+        };
+
+        (restore_focus_order..=restore_focus_orders::ROOT_FIRST).find_map(|order| match order {
+            restore_focus_orders::HISTORY => deloser.find_in_history(),
+            restore_focus_orders::DELOSER_DEFAULT => find_default(Some(deloser_element.clone())),
+            restore_focus_orders::ROOT_DEFAULT => find_default(root_element.clone()),
+            restore_focus_orders::DELOSER_FIRST => find_first(Some(deloser_element.clone())),
+            restore_focus_orders::ROOT_FIRST => find_first(root_element.clone()),
+            _ => None,
+        })
+    }
+
+    /// Returns the closest deloser the element is inside of.
+    pub fn get_deloser(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+    ) -> Option<ArcCellDeloser> {
+        let mut el = Some(element.clone());
+
+        while let Some(el_ref) = el {
+            let deloser = get_tabster_on_element(tabster, &el_ref)
+                .and_then(|tabster_on_element| tabster_on_element.borrow().deloser.clone());
+
+            if deloser.is_some() {
+                return deloser;
+            }
+
+            el = DOM::get_parent_element(Some(el_ref));
+        }
+
+        None
+    }
+}
@@ -1,8 +1,19 @@
-use crate::types;
+use crate::{
+    consts::FOCUSABLE_SELECTOR,
+    instance::get_tabster_on_element,
+    keyborg::native_focus,
+    tabster::TabsterCore,
+    types::{self, DOMAPI},
+    utils::{
+        composed_first_child, composed_last_child, composed_parent, composed_sibling,
+        is_delegates_focus_host, is_display_none, matches_selector,
+    },
+};
+use std::{cell::RefCell, sync::Arc};
 use web_sys::{
     wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt},
-    Document, Element, HtmlElement, MutationObserver, MutationRecord, Node, NodeFilter, NodeList,
-    TreeWalker,
+    Document, Element, HtmlElement, MutationObserver, MutationRecord, Node, NodeList, ShadowRoot,
+    SvgElement,
 };
 
 pub struct DOM;
@@ -16,23 +27,38 @@ impl types::DOMAPI for DOM {
         MutationObserver::new(cb.as_ref().unchecked_ref()).unwrap_throw()
     }
 
-    fn create_tree_walker(
-        doc: &Document,
-        root: &Node,
-        what_to_show: u32,
-        filter: Option<&NodeFilter>,
-    ) -> TreeWalker {
-        doc.create_tree_walker_with_what_to_show_and_filter(root, what_to_show, filter)
-            .unwrap_throw()
+    fn get_active_element(doc: &Document) -> Option<Element> {
+        let mut active_element = doc.active_element();
+
+        // The focus inside an open shadow root is retargeted to its host.
+        while let Some(shadow_active_element) = active_element
+            .as_ref()
+            .and_then(|el| el.shadow_root())
+            .and_then(|shadow_root| shadow_root.active_element())
+        {
+            active_element = Some(shadow_active_element);
+        }
+
+        active_element
     }
 
     fn get_parent_node(node: Option<Node>) -> Option<types::ParentNode> {
         node?.parent_node()
     }
 
+    fn get_composed_parent_node(node: Option<Node>) -> Option<types::ParentNode> {
+        let parent = node?.parent_node()?;
+
+        match parent.dyn_into::<ShadowRoot>() {
+            Ok(shadow_root) => Some(shadow_root.host().into()),
+            Err(parent) => Some(parent),
+        }
+    }
+
     fn get_parent_element(element: Option<web_sys::HtmlElement>) -> Option<web_sys::HtmlElement> {
-        element?
-            .parent_element()
+        Self::get_composed_parent_node(Some(element?.into()))?
+            .dyn_into::<Element>()
+            .ok()
             .map(|e| e.dyn_into().unwrap_throw())
     }
 
@@ -41,7 +67,22 @@ impl types::DOMAPI for DOM {
             return false;
         };
 
-        parent.contains(child.as_ref())
+        let mut child = child;
+
+        while let Some(node) = child {
+            if parent.contains(Some(&node)) {
+                return true;
+            }
+
+            // Trying again from the host of the shadow root the child is in.
+            child = node
+                .get_root_node()
+                .dyn_into::<ShadowRoot>()
+                .ok()
+                .map(|shadow_root| shadow_root.host().into());
+        }
+
+        false
     }
 
     fn get_first_child(node: Option<Node>) -> Option<Node> {
@@ -99,3 +140,125 @@ impl types::DOMAPI for DOM {
             .get_elements_by_name(name)
     }
 }
+
+/// `DOMBackend` over the browser document. With a Tabster instance, the
+/// accessibility checks take the Tabster props of the elements into account.
+#[derive(Clone)]
+pub struct WebDOM {
+    doc: Document,
+    tabster: Option<Arc<RefCell<TabsterCore>>>,
+}
+
+impl WebDOM {
+    pub fn new(doc: Document) -> Self {
+        Self { doc, tabster: None }
+    }
+
+    pub(crate) fn with_tabster(doc: Document, tabster: Arc<RefCell<TabsterCore>>) -> Self {
+        Self {
+            doc,
+            tabster: Some(tabster),
+        }
+    }
+}
+
+impl types::DOMBackend for WebDOM {
+    type Element = Element;
+
+    fn body(&self) -> Option<Element> {
+        self.doc.body().map(Into::into)
+    }
+
+    fn get_parent_element(&self, element: &Element) -> Option<Element> {
+        composed_parent(element)
+    }
+
+    fn get_first_child(&self, element: &Element) -> Option<Element> {
+        composed_first_child(element)
+    }
+
+    fn get_last_child(&self, element: &Element) -> Option<Element> {
+        composed_last_child(element)
+    }
+
+    fn get_next_sibling(&self, element: &Element) -> Option<Element> {
+        composed_sibling(element, 1)
+    }
+
+    fn get_previous_sibling(&self, element: &Element) -> Option<Element> {
+        composed_sibling(element, -1)
+    }
+
+    fn get_active_element(&self) -> Option<Element> {
+        DOM::get_active_element(&self.doc)
+    }
+
+    fn focus(&self, element: &Element) {
+        if let Some(element) = element.dyn_ref::<HtmlElement>() {
+            native_focus(element.clone());
+        }
+    }
+
+    fn get_attribute(&self, element: &Element, name: &str) -> Option<String> {
+        element.get_attribute(name)
+    }
+
+    fn has_attribute(&self, element: &Element, name: &str) -> bool {
+        element.has_attribute(name)
+    }
+
+    fn matches_focusable_selector(&self, element: &Element) -> bool {
+        matches_selector(element, FOCUSABLE_SELECTOR)
+    }
+
+    fn tab_index(&self, element: &Element) -> i32 {
+        if let Some(element) = element.dyn_ref::<HtmlElement>() {
+            element.tab_index()
+        } else if let Some(element) = element.dyn_ref::<SvgElement>() {
+            element.tab_index()
+        } else {
+            // MathML and other elements have no `tabIndex` in web-sys.
+            self.get_attribute(element, "tabindex")
+                .and_then(|tab_index| tab_index.trim().parse().ok())
+                .unwrap_or(-1)
+        }
+    }
+
+    fn is_delegates_focus_host(&self, element: &Element) -> bool {
+        is_delegates_focus_host(element)
+    }
+
+    fn get_bounding_client_rect(&self, element: &Element) -> types::TabsterDOMRect {
+        let rect = element.get_bounding_client_rect();
+
+        types::TabsterDOMRect {
+            left: rect.left(),
+            top: rect.top(),
+            right: rect.right(),
+            bottom: rect.bottom(),
+        }
+    }
+
+    fn is_display_none(&self, element: &Element) -> bool {
+        is_display_none(element.clone())
+    }
+
+    fn is_augmented(&self, element: &Element) -> bool {
+        self.tabster.as_ref().is_some_and(|tabster| {
+            tabster
+                .borrow()
+                .modalizer
+                .as_ref()
+                .is_some_and(|modalizer| modalizer.borrow().is_augmented(element))
+        })
+    }
+
+    fn ignore_aria_disabled(&self, element: &Element) -> bool {
+        self.tabster
+            .as_ref()
+            .and_then(|tabster| get_tabster_on_element(tabster, element))
+            .and_then(|tabster_on_element| tabster_on_element.borrow().focusable.clone())
+            .and_then(|focusable| focusable.ignore_aria_disabled)
+            .unwrap_or_default()
+    }
+}
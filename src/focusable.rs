@@ -1,24 +1,22 @@
 use crate::{
     console_log,
-    consts::FOCUSABLE_SELECTOR,
-    dom_api::DOM,
+    dom_api::{WebDOM, DOM},
     instance::get_tabster_on_element,
     root::RootAPI,
     tabster::TabsterCore,
     types::{
-        self, FindAllProps, FindFirstProps, FindFocusableOutputProps, FindFocusableProps,
-        FocusableAcceptElementState, DOMAPI,
+        self, DOMBackend, FindAllProps, FindFirstProps, FindFocusableOutputProps,
+        FindFocusableProps, FocusableAcceptElementState, DOMAPI,
     },
     utils::{
         create_element_tree_walker, get_dummy_input_container, get_last_child,
-        get_radio_button_group, is_display_none, is_radio, matches_selector, should_ignore_focus,
-        NodeFilterEnum,
+        get_radio_button_group, is_radio, should_ignore_focus, FilterResult,
     },
 };
 use std::{cell::RefCell, collections::HashMap, sync::Arc};
 use web_sys::{
     wasm_bindgen::{JsCast, UnwrapThrowExt},
-    Element, HtmlElement, HtmlInputElement, Node, SvgElement,
+    Element, HtmlElement, HtmlInputElement,
 };
 
 #[derive(Clone)]
@@ -43,6 +41,14 @@ impl FocusableAPI {
         }
     }
 
+    /// The browser DOM of the element, the focusability rules are run against.
+    fn dom(&self, el: &Element) -> Option<WebDOM> {
+        Some(WebDOM::with_tabster(
+            el.owner_document()?,
+            self.tabster.clone(),
+        ))
+    }
+
     pub fn is_focusable(
         &self,
         el: &Element,
@@ -50,103 +56,22 @@ impl FocusableAPI {
         no_visible_check: Option<bool>,
         no_accessible_check: Option<bool>,
     ) -> bool {
-        fn tab_index(el: &Element) -> i32 {
-            if let Ok(el) = el.clone().dyn_into::<HtmlElement>() {
-                el.tab_index()
-            } else if let Ok(el) = el.clone().dyn_into::<SvgElement>() {
-                el.tab_index()
-            } else {
-                unreachable!()
-            }
-        }
-        if matches_selector(&el, FOCUSABLE_SELECTOR)
-            && (include_programmatically_focusable.unwrap_or_default() || tab_index(el) != -1)
-        {
-            (no_visible_check.unwrap_or_default() || FocusableAPI::is_visible(el))
-                && (no_accessible_check.unwrap_or_default() || self.is_accessible(el))
-        } else {
-            false
-        }
-    }
-
-    fn is_visible(el: &Element) -> bool {
-        let Some(owner_document) = el.owner_document() else {
-            return false;
-        };
-        if el.node_type() != Node::ELEMENT_NODE {
-            return false;
-        }
-
-        if is_display_none(el.clone()) {
-            return false;
-        }
-
-        let rect = owner_document
-            .body()
-            .unwrap_throw()
-            .get_bounding_client_rect();
-
-        if rect.width() == 0.0 && rect.height() == 0.0 {
-            // This might happen, for example, if our <body> is in hidden <iframe>.
-            return false;
-        }
-
-        true
-    }
-
-    fn is_accessible(&self, el: &Element) -> bool {
-        let mut e = Some(el.clone());
-        loop {
-            let Some(e_ref) = e.as_ref() else {
-                break;
-            };
-
-            let tabster_on_element = get_tabster_on_element(&self.tabster, e_ref);
-
-            if self.is_hidden(e_ref) {
-                return false;
-            }
-            let Some(tabster_on_element) = tabster_on_element else {
-                return false;
-            };
-            let tabster_on_element = tabster_on_element.borrow();
-            let Some(focusable) = tabster_on_element.focusable.as_ref() else {
-                return false;
-            };
-            let ignore_disabled = focusable.ignore_aria_disabled;
-            if !ignore_disabled.unwrap_or_default() && FocusableAPI::is_disabled(e_ref) {
-                return false;
-            }
-
-            e = e.map(|e| e.parent_element()).flatten();
-        }
-
-        true
+        self.dom(el).is_some_and(|dom| {
+            dom.is_focusable(
+                el,
+                include_programmatically_focusable,
+                no_visible_check,
+                no_accessible_check,
+            )
+        })
     }
 
-    fn is_disabled(el: &Element) -> bool {
-        el.has_attribute("disabled")
-    }
-
-    fn is_hidden(&self, el: &Element) -> bool {
-        let Some(attr_val) = el.get_attribute("aria-hidden") else {
-            return false;
-        };
-
-        if attr_val.to_lowercase() == "true" {
-            let tabster = self.tabster.borrow();
-            if let Some(modalizer) = tabster.modalizer.as_ref() {
-                if !(modalizer.is_augmented)(el.clone()) {
-                    return true;
-                }
-            }
-        }
-
-        false
+    pub(crate) fn is_accessible(&self, el: &Element) -> bool {
+        self.dom(el).is_some_and(|dom| dom.is_accessible(el))
     }
 
     pub fn find_first(
-        &mut self,
+        &self,
         options: FindFirstProps,
         out: &mut FindFocusableOutputProps,
     ) -> Option<HtmlElement> {
@@ -154,7 +79,7 @@ impl FocusableAPI {
     }
 
     pub fn find_last(
-        &mut self,
+        &self,
         options: FindFirstProps,
         out: &mut FindFocusableOutputProps,
     ) -> Option<HtmlElement> {
@@ -168,7 +93,7 @@ impl FocusableAPI {
     }
 
     pub fn find_next(
-        &mut self,
+        &self,
         options: types::FindNextProps,
         out: &mut FindFocusableOutputProps,
     ) -> Option<HtmlElement> {
@@ -176,7 +101,7 @@ impl FocusableAPI {
     }
 
     pub fn find_prev(
-        &mut self,
+        &self,
         options: types::FindNextProps,
         out: &mut FindFocusableOutputProps,
     ) -> Option<HtmlElement> {
@@ -190,7 +115,7 @@ impl FocusableAPI {
     }
 
     pub fn find_default(
-        &mut self,
+        &self,
         options: types::FindDefaultProps,
         out: &mut FindFocusableOutputProps,
     ) -> Option<HtmlElement> {
@@ -211,12 +136,17 @@ impl FocusableAPI {
         )
     }
 
-    pub fn find_all(&mut self, options: FindAllProps, out: &mut FindFocusableOutputProps) {
-        self.find_elements(true, options.into(), out);
+    pub fn find_all(
+        &self,
+        options: FindAllProps,
+        out: &mut FindFocusableOutputProps,
+    ) -> Vec<HtmlElement> {
+        self.find_elements(true, options.into(), out)
+            .unwrap_or_default()
     }
 
     pub fn find_element(
-        &mut self,
+        &self,
         options: FindFocusableProps,
         out: &mut FindFocusableOutputProps,
     ) -> Option<HtmlElement> {
@@ -225,7 +155,7 @@ impl FocusableAPI {
     }
 
     fn find_elements(
-        &mut self,
+        &self,
         is_find_all: bool,
         options: FindFocusableProps,
         out: &mut FindFocusableOutputProps,
@@ -268,7 +198,7 @@ impl FocusableAPI {
             if modalizer_id.is_none() && use_active_modalizer.unwrap_or_default() {
                 let tabster = self.tabster.borrow();
                 if let Some(modalizer) = tabster.modalizer.as_ref() {
-                    modalizer.active_id.clone()
+                    modalizer.borrow().active_id.clone()
                 } else {
                     None
                 }
@@ -301,21 +231,16 @@ impl FocusableAPI {
             reject_elements_from: None,
             cached_grouppers: HashMap::new(),
             cached_radio_groups: HashMap::new(),
-            is_find_all: None,
+            is_find_all: Some(is_find_all),
             skipped_focusable: None,
         };
         let accept_element_state = Arc::new(RefCell::new(accept_element_state));
-        let Some(walker) =
-            create_element_tree_walker(&container.owner_document().unwrap_throw(), &container, {
-                let accept_element_state = accept_element_state.clone();
-                let this = self.clone();
-                move |node| {
-                    this.accept_element(node.dyn_into().unwrap_throw(), &accept_element_state)
-                }
-            })
-        else {
-            return None;
-        };
+        let dom = self.dom(&container)?;
+        let mut walker = create_element_tree_walker(&dom, &container.clone().into(), {
+            let accept_element_state = accept_element_state.clone();
+            let this = self.clone();
+            move |el: &Element| this.accept_element(el.clone(), &accept_element_state)
+        });
 
         let prepare_for_next_element = {
             let accept_element_state = accept_element_state.clone();
@@ -382,11 +307,9 @@ impl FocusableAPI {
         {
             walker.set_current_node(&current_element.clone().unwrap().into());
         } else if matches!(is_backward, Some(true)) {
-            let Some(last_child) = get_last_child(&container) else {
-                return None;
-            };
-            if self.accept_element(last_child.clone().into(), &accept_element_state)
-                == *NodeFilterEnum::FilterAccept
+            let last_child = get_last_child(&dom, &container.clone().into())?;
+            if self.accept_element(last_child.clone(), &accept_element_state)
+                == FilterResult::Accept
                 && !prepare_for_next_element(
                     Some(true),
                     &mut elements,
@@ -411,11 +334,11 @@ impl FocusableAPI {
             let node = walker.current_node();
             console_log!("find_elements loop:main {}", node.node_name());
             if matches!(is_backward, Some(true)) {
-                let node = walker.previous_node().unwrap_throw();
+                let node = walker.previous_node();
 
                 console_log!("find_elements loop node:{:#?}", node.map(|n| n.node_name()));
             } else {
-                walker.next_node().unwrap_throw();
+                walker.next_node();
             }
 
             if !prepare_for_next_element(
@@ -445,10 +368,10 @@ impl FocusableAPI {
         &self,
         element: Element,
         state: &Arc<RefCell<FocusableAcceptElementState>>,
-    ) -> u32 {
+    ) -> FilterResult {
         let mut state = state.borrow_mut();
         if matches!(state.found, Some(true)) {
-            return *NodeFilterEnum::FilterAccept;
+            return FilterResult::Accept;
         }
 
         let found_backward = state.found_backward.clone();
@@ -462,35 +385,35 @@ impl FocusableAPI {
         {
             state.found = Some(true);
             state.found_element = found_backward;
-            return *NodeFilterEnum::FilterAccept;
+            return FilterResult::Accept;
         }
 
         let container = state.container.clone();
 
         if element == *container {
-            return *NodeFilterEnum::FilterSkip;
+            return FilterResult::Skip;
         }
 
         if !DOM::node_contains(Some(container.clone().into()), Some(element.clone().into())) {
-            return *NodeFilterEnum::FilterReject;
+            return FilterResult::Reject;
         }
 
         if get_dummy_input_container(&Some(element.clone())).is_some() {
-            return *NodeFilterEnum::FilterReject;
+            return FilterResult::Reject;
         }
 
         if DOM::node_contains(
             state.reject_elements_from.clone().map(|r| r.into()),
             Some(element.clone().into()),
         ) {
-            return *NodeFilterEnum::FilterReject;
+            return FilterResult::Reject;
         }
 
         state.current_ctx =
             RootAPI::get_tabster_context(&self.tabster, &element, Default::default());
         // Tabster is opt in, if it is not managed, don't try and get do anything special
         let Some(ctx) = state.current_ctx.clone() else {
-            return *NodeFilterEnum::FilterSkip;
+            return FilterResult::Skip;
         };
 
         if should_ignore_focus(&element) {
@@ -503,7 +426,7 @@ impl FocusableAPI {
                 state.skipped_focusable = Some(true);
             }
 
-            return *NodeFilterEnum::FilterSkip;
+            return FilterResult::Skip;
         }
 
         // We assume iframes are focusable because native tab behaviour would tab inside.
@@ -520,7 +443,7 @@ impl FocusableAPI {
             let active_id = {
                 let tabster = self.tabster.borrow();
                 if let Some(modalizer) = &tabster.modalizer {
-                    modalizer.active_id.clone()
+                    modalizer.borrow().active_id.clone()
                 } else {
                     None
                 }
@@ -532,9 +455,9 @@ impl FocusableAPI {
                 state.found_element = Some(element.clone());
                 state.reject_elements_from = Some(element);
 
-                return *NodeFilterEnum::FilterAccept;
+                return FilterResult::Accept;
             } else {
-                return *NodeFilterEnum::FilterReject;
+                return FilterResult::Reject;
             }
         }
 
@@ -543,7 +466,7 @@ impl FocusableAPI {
                 state.skipped_focusable = Some(true);
             }
 
-            return *NodeFilterEnum::FilterReject;
+            return FilterResult::Reject;
         }
 
         let from_ctx = if let Some(from_ctx) = state.from_ctx.clone() {
@@ -559,10 +482,37 @@ impl FocusableAPI {
         let mut groupper = ctx.groupper;
         let mut mover = ctx.mover;
 
+        // The parts inside an uncontrolled area are managed by the area itself,
+        // when coming from outside, the area is entered at its edge as the browser would do.
+        if let Some(uncontrolled) = ctx.uncontrolled.as_ref() {
+            if !DOM::node_contains(
+                Some(uncontrolled.clone().into()),
+                Some(state.from.clone().into()),
+            ) {
+                let is_inside = |element: Option<HtmlElement>| {
+                    DOM::node_contains(Some(uncontrolled.clone().into()), element.map(Into::into))
+                };
+
+                if groupper
+                    .as_ref()
+                    .is_some_and(|groupper| is_inside(groupper.borrow().get_element()))
+                {
+                    groupper = None;
+                }
+
+                if mover
+                    .as_ref()
+                    .is_some_and(|mover| is_inside(mover.borrow().get_element()))
+                {
+                    mover = None;
+                }
+            }
+        }
+
         let mut result = {
             let tabster = self.tabster.borrow();
             if let Some(modalizer) = &tabster.modalizer {
-                modalizer.accept_element(&element, &mut state)
+                modalizer.borrow().accept_element(&element, &mut state)
             } else {
                 None
             }
@@ -574,19 +524,19 @@ impl FocusableAPI {
 
         if result.is_none() && (from_mover.is_some() || groupper.is_some() || mover.is_some()) {
             let groupper_element = if let Some(groupper) = &groupper {
-                let groupper = groupper.borrow_mut();
+                let groupper = groupper.borrow();
                 groupper.get_element()
             } else {
                 None
             };
             let from_mover_element = if let Some(from_mover) = &from_mover {
-                let from_mover = from_mover.borrow_mut();
+                let from_mover = from_mover.borrow();
                 from_mover.get_element()
             } else {
                 None
             };
             let mut mover_element = if let Some(mover) = &mover {
-                let mover = mover.borrow_mut();
+                let mover = mover.borrow();
                 mover.get_element()
             } else {
                 None
@@ -646,7 +596,7 @@ impl FocusableAPI {
             }
 
             if let Some(groupper) = &groupper {
-                let mut groupper = groupper.borrow_mut();
+                let groupper = groupper.borrow();
                 result = groupper.accept_element(&element, &mut state);
             }
 
@@ -658,19 +608,19 @@ impl FocusableAPI {
 
         if result.is_none() {
             result = if (state.accept_condition)(element.clone().dyn_into().unwrap_throw()) {
-                Some(*NodeFilterEnum::FilterAccept)
+                Some(FilterResult::Accept)
             } else {
-                Some(*NodeFilterEnum::FilterSkip)
+                Some(FilterResult::Skip)
             };
 
-            if result == Some(*NodeFilterEnum::FilterSkip)
+            if result == Some(FilterResult::Skip)
                 && self.is_focusable(&element, Some(false), Some(true), Some(true))
             {
                 state.skipped_focusable = Some(true);
             }
         }
 
-        if result == Some(*NodeFilterEnum::FilterAccept) && !state.found.unwrap_or_default() {
+        if result == Some(FilterResult::Accept) && !state.found.unwrap_or_default() {
             if !state.is_find_all.unwrap_throw()
                 && is_radio(&element)
                 && !element
@@ -700,7 +650,7 @@ impl FocusableAPI {
 
                 if radio_group.checked.is_some() && radio_group.checked != Some(element) {
                     // Currently found element is a radio button in a group that has another radio button checked.
-                    return *NodeFilterEnum::FilterSkip;
+                    return FilterResult::Skip;
                 }
             }
 
@@ -710,7 +660,7 @@ impl FocusableAPI {
                 // then it goes inside. So, if the container is accepted, we remember it,
                 // but allowing the TreeWalker to check inside.
                 state.found_backward = Some(element.clone());
-                result = Some(*NodeFilterEnum::FilterSkip);
+                result = Some(FilterResult::Skip);
             } else {
                 state.found = Some(true);
                 state.found_element = Some(element.clone());
@@ -1,17 +1,22 @@
 use crate::{
     dom_api::DOM,
+    groupper_move_focus_actions,
     instance::get_tabster_on_element,
     keyborg::native_focus,
+    keys,
     root::RootAPI,
     state::focused_element::FocusedElementState,
     tabster::TabsterCore,
     types::{self, CachedGroupper, FindFirstProps, GetWindow, DOMAPI},
     utils::{
-        get_adjacent_element, get_dummy_input_container, DummyInputManager, NodeFilterEnum,
+        get_adjacent_element, get_dummy_input_container, DummyInputManager, FilterResult,
         TabsterPart, WeakHTMLElement,
     },
-    web::set_timeout,
-    GroupperTabbabilities,
+    web::{
+        add_event_listener, add_event_listener_with_bool, dispatch_custom_event, set_timeout,
+        EventListenerHandle,
+    },
+    GroupperTabbabilities, GROUPPER_ACTIVE_EVENT_NAME, GROUPPER_MOVE_FOCUS_EVENT_NAME,
 };
 use std::{
     cell::{RefCell, RefMut},
@@ -21,7 +26,7 @@ use std::{
 };
 use web_sys::{
     wasm_bindgen::{JsCast, UnwrapThrowExt},
-    Element, HtmlElement,
+    CustomEvent, Element, HtmlElement, KeyboardEvent, MouseEvent,
 };
 
 struct GroupperDummyManager(DummyInputManager);
@@ -38,17 +43,17 @@ impl GroupperDummyManager {
         dummy_input_manager.set_handlers(
             Some(Box::new(move |dummy_input, is_backward, related_target| {
                 if let Some(container) = element.get() {
-                    if let Some(input) = dummy_input.input {
+                    if let Some(input) = dummy_input.input() {
                         if let Some(ctx) =
                             RootAPI::get_tabster_context(&tabster, &input, Default::default())
                         {
-                            let mut groupper = groupper.borrow_mut();
-                            let mut next = if let Some(next_tabbable) = groupper.find_next_tabbable(
+                            let next_tabbable = groupper.borrow_mut().find_next_tabbable(
                                 related_target,
                                 None,
                                 Some(is_backward),
                                 Some(true),
-                            ) {
+                            );
+                            let mut next = if let Some(next_tabbable) = next_tabbable {
                                 next_tabbable.element
                             } else {
                                 None
@@ -90,8 +95,9 @@ pub type ArcCellGroupper = Arc<RefCell<Groupper>>;
 
 pub struct Groupper {
     part: TabsterPart<types::GroupperProps>,
+    win: Arc<GetWindow>,
     should_tab_inside: bool,
-    first: Option<HtmlElement>,
+    first: RefCell<Option<WeakHTMLElement>>,
     dummy_manager: Option<GroupperDummyManager>,
 }
 
@@ -116,10 +122,12 @@ impl Groupper {
         props: types::GroupperProps,
         sys: Option<types::SysProps>,
     ) -> Arc<RefCell<Self>> {
+        let win = tabster.borrow().get_window.clone();
         let this = Arc::new(RefCell::new(Self {
             part: TabsterPart::new(tabster.clone(), element.clone(), props),
+            win,
             should_tab_inside: false,
-            first: None,
+            first: Default::default(),
             dummy_manager: None,
         }));
 
@@ -139,13 +147,14 @@ impl Groupper {
         };
         {
             let mut this = this.borrow_mut();
+            this.make_tabbable(false);
             this.dummy_manager = dummy_manager;
         }
         this
     }
 
     pub(crate) fn find_next_tabbable(
-        &mut self,
+        &self,
         current_element: Option<HtmlElement>,
         reference_element: Option<HtmlElement>,
         is_backward: Option<bool>,
@@ -205,14 +214,14 @@ impl Groupper {
 
             let tabster = self.tabster.borrow();
             let focusable = tabster.focusable.clone().unwrap_throw();
-            let mut focusable = focusable.borrow_mut();
+            let focusable = focusable.borrow();
             next = if is_backward.unwrap_or_default() {
                 focusable.find_prev(find_props, &mut find_props_out)
             } else {
                 focusable.find_next(find_props, &mut find_props_out)
             };
 
-            out_of_dom_order = find_props_out.out_of_dom_order.unwrap_throw();
+            out_of_dom_order = find_props_out.out_of_dom_order.unwrap_or_default();
 
             if next.is_none()
                 && self.props.tabbability == Some(*GroupperTabbabilities::LimitedTrapFocus)
@@ -242,7 +251,7 @@ impl Groupper {
         })
     }
 
-    pub fn is_active(&mut self, no_if_first_is_focused: Option<bool>) -> Option<bool> {
+    pub fn is_active(&self, no_if_first_is_focused: Option<bool>) -> Option<bool> {
         let element = self.part.get_element();
         let mut is_parent_active = true;
 
@@ -289,7 +298,7 @@ impl Groupper {
         ret
     }
 
-    fn get_first(&mut self, or_container: bool) -> Option<HtmlElement> {
+    fn get_first(&self, or_container: bool) -> Option<HtmlElement> {
         let mut first = None::<HtmlElement>;
 
         if let Some(groupper_element) = self.get_element() {
@@ -297,13 +306,13 @@ impl Groupper {
                 let tabster = self.tabster.borrow();
                 tabster.focusable.clone().unwrap_throw()
             };
-            let mut focusable = focusable.borrow_mut();
+            let focusable = focusable.borrow();
 
             if or_container && focusable.is_focusable(&groupper_element, None, None, None) {
                 return Some(groupper_element);
             }
 
-            first = self.first.clone();
+            first = self.first.borrow().as_ref().and_then(|first| first.get());
 
             if first.is_none() {
                 first = focusable.find_first(
@@ -324,16 +333,36 @@ impl Groupper {
         first
     }
 
-    fn set_first(&mut self, element: Option<HtmlElement>) {
-        if let Some(element) = element {
-            self.first = Some(element);
-        } else {
-            self.first = None;
+    /// Returns `true` when the groupper has become active or inactive, the
+    /// caller dispatches `GROUPPER_ACTIVE_EVENT_NAME` once the groupper is not
+    /// borrowed anymore (see `GroupperAPI::dispatch_active`).
+    pub fn make_tabbable(&mut self, is_tabbable: bool) -> bool {
+        let was_active = self.is_tab_inside_active();
+        self.should_tab_inside = is_tabbable || self.props.tabbability.unwrap_or_default() == 0;
+
+        was_active != self.is_tab_inside_active()
+    }
+
+    /// Whether the focus is let inside the groupper which limits it.
+    fn is_tab_inside_active(&self) -> bool {
+        self.should_tab_inside && self.props.tabbability.unwrap_or_default() > 0
+    }
+
+    pub fn dispose(&mut self) {
+        if let Some(mut dummy_manager) = self.dummy_manager.take() {
+            dummy_manager.0.dispose();
         }
+
+        self.set_first(None);
+    }
+
+    fn set_first(&self, element: Option<HtmlElement>) {
+        *self.first.borrow_mut() =
+            element.map(|element| WeakHTMLElement::new(self.win.clone(), element, None));
     }
 
     fn get_is_active(
-        &mut self,
+        &self,
         state: &mut RefMut<'_, types::FocusableAcceptElementState>,
         groupper_id: &str,
     ) -> Option<bool> {
@@ -358,10 +387,10 @@ impl Groupper {
     }
 
     pub fn accept_element(
-        &mut self,
+        &self,
         element: &Element,
         state: &mut RefMut<'_, types::FocusableAcceptElementState>,
-    ) -> Option<u32> {
+    ) -> Option<FilterResult> {
         let parent_element = DOM::get_parent_element(self.get_element());
         let parent_ctx = if let Some(parent_element) = &parent_element {
             RootAPI::get_tabster_context(&self.tabster, &parent_element, Default::default())
@@ -395,7 +424,7 @@ impl Groupper {
             {
                 // Do not fall into a child groupper of inactive parent groupper if it's in the scope of the search.
                 state.skipped_focusable = Some(true);
-                return Some(*NodeFilterEnum::FilterReject);
+                return Some(FilterResult::Reject);
             }
         }
 
@@ -423,7 +452,7 @@ impl Groupper {
                             && parent_groupper_element != Some(state.container.clone())
                         {
                             state.skipped_focusable = Some(true);
-                            return Some(*NodeFilterEnum::FilterReject);
+                            return Some(FilterResult::Reject);
                         }
                     }
                 }
@@ -435,7 +464,7 @@ impl Groupper {
                     )
                 {
                     state.skipped_focusable = Some(true);
-                    return Some(*NodeFilterEnum::FilterReject);
+                    return Some(FilterResult::Reject);
                 }
 
                 let cached = state.cached_grouppers.get_mut(self.id()).unwrap_throw();
@@ -454,9 +483,9 @@ impl Groupper {
                         if first != state.from {
                             state.found = Some(true);
                             state.found_element = Some(first);
-                            return Some(*NodeFilterEnum::FilterAccept);
+                            return Some(FilterResult::Accept);
                         } else {
-                            return Some(*NodeFilterEnum::FilterReject);
+                            return Some(FilterResult::Reject);
                         }
                     }
                 }
@@ -466,20 +495,374 @@ impl Groupper {
     }
 }
 
+type CurrentGrouppers = Arc<RefCell<HashMap<String, ArcCellGroupper>>>;
+
 pub struct GroupperAPI {
     tabster: Arc<RefCell<TabsterCore>>,
     update_timer: Arc<RefCell<Option<i32>>>,
     win: Arc<GetWindow>,
+    current: CurrentGrouppers,
     grouppers: HashMap<String, Arc<RefCell<Groupper>>>,
+    event_listener_handle_keydown: Arc<RefCell<Option<EventListenerHandle>>>,
+    event_listener_handle_mousedown: Arc<RefCell<Option<EventListenerHandle>>>,
+    event_listener_handle_move_focus: Arc<RefCell<Option<EventListenerHandle>>>,
 }
 
 impl GroupperAPI {
     pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
+        let update_timer: Arc<RefCell<Option<i32>>> = Default::default();
+        let current: CurrentGrouppers = Default::default();
+        let event_listener_handle_keydown: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+        let event_listener_handle_mousedown: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+        let event_listener_handle_move_focus: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+
+        tabster.borrow_mut().queue_init({
+            let tabster = tabster.clone();
+            let get_window = get_window.clone();
+            let update_timer = update_timer.clone();
+            let current = current.clone();
+            let event_listener_handle_keydown = event_listener_handle_keydown.clone();
+            let event_listener_handle_mousedown = event_listener_handle_mousedown.clone();
+            let event_listener_handle_move_focus = event_listener_handle_move_focus.clone();
+            move || {
+                let win = get_window();
+                let doc = win.document().unwrap_throw();
+
+                let on_focus = {
+                    let tabster = tabster.clone();
+                    let update_timer = update_timer.clone();
+                    let current = current.clone();
+                    move |element: HtmlElement| {
+                        Self::update_current(
+                            tabster.clone(),
+                            &current,
+                            &update_timer,
+                            &element,
+                            Some(true),
+                            Some(true),
+                        );
+                    }
+                };
+
+                if let Some(active_element) = DOM::get_active_element(&doc) {
+                    if let Ok(active_element) = active_element.dyn_into::<HtmlElement>() {
+                        on_focus.clone()(active_element);
+                    }
+                }
+
+                if let Some(focused_element) = tabster.borrow_mut().focused_element.as_mut() {
+                    focused_element.subscribe(move |_, element, _| {
+                        if let Some(element) = element {
+                            on_focus(element);
+                        }
+                    });
+                }
+
+                *event_listener_handle_mousedown.borrow_mut() = Some(add_event_listener_with_bool(
+                    doc,
+                    "mousedown",
+                    {
+                        let tabster = tabster.clone();
+                        let update_timer = update_timer.clone();
+                        let current = current.clone();
+                        move |event: MouseEvent| {
+                            let focusable = tabster.borrow().focusable.clone().unwrap_throw();
+                            // The target is retargeted to the host for the clicks inside shadow roots.
+                            let mut target =
+                                event.composed_path().get(0).dyn_into::<HtmlElement>().ok();
+
+                            while let Some(el) = target.as_ref() {
+                                if focusable.borrow().is_focusable(el, None, None, None) {
+                                    break;
+                                }
+                                target = DOM::get_parent_element(Some(el.clone()));
+                            }
+
+                            if let Some(target) = target {
+                                Self::update_current(
+                                    tabster.clone(),
+                                    &current,
+                                    &update_timer,
+                                    &target,
+                                    Some(true),
+                                    None,
+                                );
+                            }
+                        }
+                    },
+                    true,
+                ));
+
+                *event_listener_handle_keydown.borrow_mut() = Some(add_event_listener_with_bool(
+                    win.clone(),
+                    "keydown",
+                    {
+                        let tabster = tabster.clone();
+                        move |event: KeyboardEvent| {
+                            Self::on_key_down(&tabster, event);
+                        }
+                    },
+                    true,
+                ));
+
+                *event_listener_handle_move_focus.borrow_mut() = Some(add_event_listener(
+                    win,
+                    GROUPPER_MOVE_FOCUS_EVENT_NAME,
+                    move |event: CustomEvent| {
+                        Self::on_move_focus(&tabster, event);
+                    },
+                ));
+            }
+        });
+
         Self {
             tabster,
-            update_timer: Default::default(),
+            update_timer,
             win: get_window,
+            current,
             grouppers: HashMap::new(),
+            event_listener_handle_keydown,
+            event_listener_handle_mousedown,
+            event_listener_handle_move_focus,
+        }
+    }
+
+    pub fn dispose(&mut self) {
+        if let Some(handle) = self.event_listener_handle_keydown.borrow_mut().take() {
+            handle.remove();
+        }
+
+        if let Some(handle) = self.event_listener_handle_mousedown.borrow_mut().take() {
+            handle.remove();
+        }
+
+        if let Some(handle) = self.event_listener_handle_move_focus.borrow_mut().take() {
+            handle.remove();
+        }
+
+        if let Some(timer) = self.update_timer.borrow_mut().take() {
+            (self.win)().clear_timeout_with_handle(timer);
+        }
+
+        self.current.borrow_mut().clear();
+        self.grouppers.clear();
+    }
+
+    fn on_key_down(tabster: &Arc<RefCell<TabsterCore>>, event: KeyboardEvent) {
+        let key = event.key();
+        if key != keys::ENTER && key != keys::ESCAPE {
+            return;
+        }
+
+        // Give a chance to other listeners to handle the event.
+        if event.ctrl_key() || event.alt_key() || event.shift_key() || event.meta_key() {
+            return;
+        }
+
+        let element = {
+            let tabster = tabster.borrow();
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        if let Some(element) = element {
+            Self::handle_key_press(tabster, element, event, None);
+        }
+    }
+
+    fn on_move_focus(tabster: &Arc<RefCell<TabsterCore>>, event: CustomEvent) {
+        let element = event.composed_path().get(0).dyn_into::<HtmlElement>().ok();
+        let detail =
+            serde_wasm_bindgen::from_value::<types::GroupperMoveFocusEventDetail>(event.detail());
+
+        let (Some(element), Ok(detail)) = (element, detail) else {
+            return;
+        };
+
+        if event.default_prevented() {
+            return;
+        }
+
+        match detail.action {
+            groupper_move_focus_actions::ENTER => {
+                Self::enter_groupper(tabster, &element, None);
+            }
+            groupper_move_focus_actions::ESCAPE => {
+                Self::escape_groupper(tabster, &element, None, None);
+            }
+            _ => return,
+        }
+
+        event.stop_immediate_propagation();
+    }
+
+    pub(crate) fn handle_key_press(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: HtmlElement,
+        event: KeyboardEvent,
+        from_modalizer: Option<bool>,
+    ) {
+        let Some(ctx) = RootAPI::get_tabster_context(tabster, &element, Default::default()) else {
+            return;
+        };
+
+        if ctx.groupper.is_none() && ctx.modalizer_in_groupper.is_none() {
+            return;
+        }
+
+        if (ctx.ignore_keydown)(event.clone()) {
+            return;
+        }
+
+        let key = event.key();
+        if key == keys::ENTER {
+            Self::enter_groupper(tabster, &element, Some(event));
+        } else if key == keys::ESCAPE {
+            // We will handle Esc asynchronously, if something in the application will
+            // move focus during the keypress handling, we will not interfere.
+            let get_focused_element = {
+                let tabster = tabster.clone();
+                move || {
+                    let tabster = tabster.borrow();
+                    tabster
+                        .focused_element
+                        .as_ref()
+                        .and_then(|focused_element| focused_element.get_focused_element())
+                }
+            };
+            let focused_element = get_focused_element();
+            let win = (tabster.borrow().get_window)();
+            let tabster = tabster.clone();
+
+            set_timeout(
+                &win,
+                move || {
+                    let from_modalizer = from_modalizer.unwrap_or_default();
+                    if focused_element != get_focused_element()
+                        && (!from_modalizer || focused_element.is_none())
+                    {
+                        // Something else in the application has moved focus, we will not handle Esc.
+                        return;
+                    }
+
+                    Self::escape_groupper(
+                        &tabster,
+                        &element,
+                        Some(event.clone()),
+                        Some(from_modalizer),
+                    );
+                },
+                0,
+            );
+        }
+    }
+
+    fn enter_groupper(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+        related_event: Option<KeyboardEvent>,
+    ) -> Option<HtmlElement> {
+        let ctx = RootAPI::get_tabster_context(tabster, element, Default::default())?;
+        let groupper = ctx.groupper.or(ctx.modalizer_in_groupper)?;
+        let groupper_element = groupper.borrow().get_element()?;
+
+        if *element != groupper_element {
+            let delegated = groupper.borrow().props.delegated.unwrap_or_default();
+
+            if !delegated || groupper.borrow().get_first(false).as_ref() != Some(element) {
+                return None;
+            }
+        }
+
+        let focusable = tabster.borrow().focusable.clone().unwrap_throw();
+        let next = focusable.borrow().find_next(
+            types::FindNextProps {
+                current_element: Some(element.clone()),
+                reference_element: None,
+                container: groupper_element.clone(),
+                ignore_accessibility: None,
+                use_active_modalizer: Some(true),
+            },
+            &mut Default::default(),
+        )?;
+
+        if let Some(related_event) = related_event {
+            related_event.prevent_default();
+            related_event.stop_immediate_propagation();
+        }
+
+        native_focus(next.clone());
+
+        Some(next)
+    }
+
+    fn escape_groupper(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+        related_event: Option<KeyboardEvent>,
+        from_modalizer: Option<bool>,
+    ) -> Option<HtmlElement> {
+        let ctx = RootAPI::get_tabster_context(tabster, element, Default::default())?;
+        let mut groupper = ctx.groupper.or(ctx.modalizer_in_groupper);
+        let groupper_element = groupper.as_ref()?.borrow().get_element()?;
+
+        if !DOM::node_contains(
+            Some(groupper_element.clone().into()),
+            Some(element.clone().into()),
+        ) {
+            return None;
+        }
+
+        let next = if *element != groupper_element || from_modalizer.unwrap_or_default() {
+            groupper
+                .as_ref()
+                .and_then(|groupper| groupper.borrow().get_first(true))
+        } else {
+            let parent_element = DOM::get_parent_element(Some(groupper_element));
+            let parent_ctx = parent_element.and_then(|parent_element| {
+                RootAPI::get_tabster_context(tabster, &parent_element, Default::default())
+            });
+            groupper = parent_ctx.and_then(|parent_ctx| parent_ctx.groupper);
+            groupper
+                .as_ref()
+                .and_then(|groupper| groupper.borrow().get_first(true))
+        }?;
+
+        // The groupper which got the focus on its element is not active anymore.
+        let deactivated = groupper.filter(|groupper| groupper.borrow_mut().make_tabbable(false));
+
+        if let Some(related_event) = related_event {
+            related_event.prevent_default();
+            related_event.stop_immediate_propagation();
+        }
+
+        native_focus(next.clone());
+
+        if let Some(groupper) = deactivated {
+            Self::dispatch_active(&groupper);
+        }
+
+        Some(next)
+    }
+
+    /// Lets the application know the groupper has become active or inactive.
+    fn dispatch_active(groupper: &ArcCellGroupper) {
+        let (element, active) = {
+            let groupper = groupper.borrow();
+            (groupper.get_element(), groupper.is_tab_inside_active())
+        };
+
+        if let Some(element) = element {
+            dispatch_custom_event(
+                &element,
+                GROUPPER_ACTIVE_EVENT_NAME,
+                &types::GroupperActiveEventDetail { active },
+            );
         }
     }
 
@@ -522,13 +905,17 @@ impl GroupperAPI {
                 };
                 if update_timer_is_none {
                     let update_timer = self.update_timer.clone();
+                    let update_timer_handle = self.update_timer.clone();
+                    let current = self.current.clone();
                     let mut update_timer_ref = self.update_timer.try_borrow_mut().unwrap_throw();
                     let tabster = self.tabster.clone();
                     let timer = set_timeout(
                         &(self.win)(),
                         move || {
-                            let mut update_timer = update_timer.try_borrow_mut().unwrap_throw();
-                            *update_timer = None;
+                            {
+                                let mut update_timer = update_timer.try_borrow_mut().unwrap_throw();
+                                *update_timer = None;
+                            }
                             let fe = {
                                 let tabster = tabster.borrow();
                                 if let Some(fe) = tabster.focused_element.as_ref() {
@@ -541,6 +928,8 @@ impl GroupperAPI {
                             if Some(&focused_element) == fe.as_ref() {
                                 Self::update_current(
                                     tabster.clone(),
+                                    &current,
+                                    &update_timer_handle,
                                     &focused_element,
                                     Some(true),
                                     Some(true),
@@ -559,58 +948,78 @@ impl GroupperAPI {
 
     fn update_current(
         tabster: Arc<RefCell<TabsterCore>>,
+        current: &CurrentGrouppers,
+        update_timer: &Arc<RefCell<Option<i32>>>,
         element: &HtmlElement,
         include_target: Option<bool>,
         check_target: Option<bool>,
     ) {
-        // if (this._updateTimer) {
-        //     this._win().clearTimeout(this._updateTimer);
-        //     delete this._updateTimer;
-        // }
+        if let Some(timer) = update_timer.borrow_mut().take() {
+            (tabster.borrow().get_window)().clear_timeout_with_handle(timer);
+        }
 
         let mut new_ids = HashSet::new();
+        let mut changed = vec![];
         let mut is_target = true;
         let mut el = Some(element.clone());
 
-        loop {
-            let Some(el_ref) = el.as_ref() else {
-                break;
-            };
-            if let Some(tabster_on_element) = get_tabster_on_element(&tabster, el_ref) {
-                let tabster_on_element = tabster_on_element.borrow();
-                if let Some(groupper) = tabster_on_element.groupper.as_ref() {
-                    let groupper = groupper.borrow();
-                    new_ids.insert(groupper.id.clone());
+        while let Some(el_ref) = el {
+            let groupper = get_tabster_on_element(&tabster, &el_ref)
+                .and_then(|tabster_on_element| tabster_on_element.borrow().groupper.clone());
 
-                    if is_target && check_target.unwrap_or_default() && el_ref != element {
-                        is_target = false;
-                    }
+            if let Some(groupper) = groupper {
+                let id = groupper.borrow().id.clone();
+                new_ids.insert(id.clone());
 
-                    if include_target.unwrap_or_default() || !is_target {
-                        //             this._current[groupper.id] = groupper;
-                        //             const isTabbable =
-                        //                 groupper.isActive() ||
-                        //                 (element !== el &&
-                        //                     (!groupper.getProps().delegated ||
-                        //                         groupper.getFirst(false) !== element));
+                if is_target && check_target.unwrap_or_default() && el_ref != *element {
+                    is_target = false;
+                }
 
-                        //             groupper.makeTabbable(isTabbable);
+                if include_target.unwrap_or_default() || !is_target {
+                    current.borrow_mut().insert(id, groupper.clone());
+
+                    // The search for the first focusable looks into the groupper
+                    // itself, it must not be mutably borrowed meanwhile.
+                    let is_tabbable = {
+                        let groupper = groupper.borrow();
+                        groupper.is_active(None).unwrap_or_default()
+                            || (*element != el_ref
+                                && (!groupper.props.delegated.unwrap_or_default()
+                                    || groupper.get_first(false).as_ref() != Some(element)))
+                    };
+
+                    if groupper.borrow_mut().make_tabbable(is_tabbable) {
+                        changed.push(groupper.clone());
                     }
-
-                    is_target = false;
                 }
+
+                is_target = false;
             }
-            el = DOM::get_parent_element(Some(el_ref.clone()));
+
+            el = DOM::get_parent_element(Some(el_ref));
         }
 
-        // for (const id of Object.keys(this._current)) {
-        //     const groupper = this._current[id];
+        let lost: Vec<_> = current
+            .borrow()
+            .iter()
+            .filter(|(id, _)| !new_ids.contains(*id))
+            .map(|(id, groupper)| (id.clone(), groupper.clone()))
+            .collect();
+
+        for (id, groupper) in lost {
+            {
+                let mut groupper_ref = groupper.borrow_mut();
+                if groupper_ref.make_tabbable(false) {
+                    changed.push(groupper.clone());
+                }
+                groupper_ref.set_first(None);
+            }
+            current.borrow_mut().remove(&id);
+        }
 
-        //     if (!(groupper.id in newIds)) {
-        //         groupper.makeTabbable(false);
-        //         groupper.setFirst(undefined);
-        //         delete this._current[id];
-        //     }
-        // }
+        // The listeners might move the focus, nothing is borrowed anymore.
+        for groupper in changed {
+            Self::dispatch_active(&groupper);
+        }
     }
 }
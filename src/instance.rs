@@ -1,6 +1,7 @@
 use crate::{
     console_error, console_log,
     consts::TABSTER_ATTRIBUTE_NAME,
+    observed::ObservedElementAPI,
     tabster::TabsterCore,
     types::{self, TabsterAttributeOnElement, TabsterAttributeProps, TabsterOnElement},
     web::console_log_node,
@@ -81,13 +82,49 @@ pub fn update_tabster_by_attribute(
     let mut entry = entry.borrow_mut();
     let some_entry_tabster = Arc::new(RefCell::new(TabsterOnElement::default()));
     let tabster_on_element = entry.tabster.clone().unwrap_or(some_entry_tabster);
-    // const oldTabsterProps = entry.attr?.object || {};
+    let old_tabster_props = entry.attr.as_ref().map(|attr| attr.object.clone());
     let some_new_attr_object = Arc::new(TabsterAttributeProps::default());
     let new_tabster_props = if let Some(new_attr) = &new_attr {
         new_attr.object.clone()
     } else {
         some_new_attr_object
     };
+    drop(tabster_ref);
+
+    if old_tabster_props
+        .as_ref()
+        .is_some_and(|props| props.modalizer.is_some())
+        && new_tabster_props.modalizer.is_none()
+    {
+        let modalizer = tabster_on_element.borrow_mut().modalizer.take();
+        let modalizer_api = tabster.borrow().modalizer.clone();
+        if let (Some(modalizer), Some(modalizer_api)) = (modalizer, modalizer_api) {
+            modalizer_api.borrow_mut().dispose_modalizer(&modalizer);
+        }
+    }
+
+    if old_tabster_props
+        .as_ref()
+        .is_some_and(|props| props.deloser.is_some())
+        && new_tabster_props.deloser.is_none()
+    {
+        let deloser = tabster_on_element.borrow_mut().deloser.take();
+        let deloser_api = tabster.borrow().deloser.clone();
+        if let (Some(deloser), Some(deloser_api)) = (deloser, deloser_api) {
+            deloser_api.borrow_mut().dispose_deloser(&deloser);
+        }
+    }
+
+    if old_tabster_props
+        .as_ref()
+        .is_some_and(|props| props.restorer.is_some())
+        && new_tabster_props.restorer.is_none()
+    {
+        let restorer = tabster_on_element.borrow_mut().restorer.take();
+        if let Some(restorer) = restorer {
+            restorer.borrow_mut().dispose();
+        }
+    }
 
     if let Some(new_tabster_props_root) = &new_tabster_props.root {
         let sys = new_tabster_props.sys.clone();
@@ -98,8 +135,7 @@ pub fn update_tabster_by_attribute(
             let mut root = root.borrow_mut();
             root.set_props(new_tabster_props_root.clone());
         } else {
-            let root = tabster_ref.root.clone();
-            drop(tabster_ref);
+            let root = tabster.borrow().root.clone();
             if let Some(root) = root {
                 console_log_node(&element);
                 console_log!("root.create_root");
@@ -124,8 +160,7 @@ pub fn update_tabster_by_attribute(
                 .borrow_mut()
                 .set_props(new_tabster_props_groupper.clone());
         } else {
-            let groupper = tabster_ref.groupper.clone();
-            drop(tabster_ref);
+            let groupper = tabster.borrow().groupper.clone();
             if let Some(groupper) = groupper {
                 let mut groupper = groupper.borrow_mut();
                 tabster_on_element.groupper = Some(groupper.create_groupper(
@@ -147,8 +182,7 @@ pub fn update_tabster_by_attribute(
                 .borrow_mut()
                 .set_props(new_tabster_props_mover.clone());
         } else {
-            let mover = tabster_ref.mover.clone();
-            drop(tabster_ref);
+            let mover = tabster.borrow().mover.clone();
             if let Some(mover) = mover {
                 let mut mover = mover.borrow_mut();
                 tabster_on_element.mover =
@@ -159,6 +193,89 @@ pub fn update_tabster_by_attribute(
         }
     }
 
+    // Modalizers usually share the node with a Groupper (a dialog which is
+    // also a group), so they are not a part of the chain above.
+    if let Some(new_tabster_props_modalizer) = &new_tabster_props.modalizer {
+        let mut tabster_on_element = tabster_on_element.borrow_mut();
+        if let Some(modalizer) = &tabster_on_element.modalizer {
+            modalizer
+                .borrow_mut()
+                .set_props(new_tabster_props_modalizer.clone());
+        } else {
+            let modalizer = tabster.borrow().modalizer.clone();
+            if let Some(modalizer) = modalizer {
+                tabster_on_element.modalizer = Some(
+                    modalizer
+                        .borrow_mut()
+                        .create_modalizer(element, new_tabster_props_modalizer.clone()),
+                );
+            } else if cfg!(debug_assertions) {
+                console_error!(
+                    "Modalizer API used before initialization, please call `getModalizer()`"
+                );
+            }
+        }
+    }
+
+    if let Some(new_tabster_props_deloser) = &new_tabster_props.deloser {
+        let mut tabster_on_element = tabster_on_element.borrow_mut();
+        if let Some(deloser) = &tabster_on_element.deloser {
+            deloser
+                .borrow_mut()
+                .set_props(new_tabster_props_deloser.clone());
+        } else {
+            let deloser = tabster.borrow().deloser.clone();
+            if let Some(deloser) = deloser {
+                tabster_on_element.deloser = Some(
+                    deloser
+                        .borrow_mut()
+                        .create_deloser(element, new_tabster_props_deloser.clone()),
+                );
+            } else if cfg!(debug_assertions) {
+                console_error!(
+                    "Deloser API used before initialization, please call `getDeloser()`"
+                );
+            }
+        }
+    }
+
+    if let Some(new_tabster_props_restorer) = &new_tabster_props.restorer {
+        let mut tabster_on_element = tabster_on_element.borrow_mut();
+        if let Some(restorer) = &tabster_on_element.restorer {
+            restorer
+                .borrow_mut()
+                .set_props(new_tabster_props_restorer.clone());
+        } else {
+            let restorer = tabster.borrow().restorer.clone();
+            if let Some(restorer) = restorer {
+                tabster_on_element.restorer = Some(
+                    restorer
+                        .borrow_mut()
+                        .create_restorer(element, new_tabster_props_restorer.clone()),
+                );
+            } else if cfg!(debug_assertions) {
+                console_error!(
+                    "Restorer API used before initialization, please call `getRestorer()`"
+                );
+            }
+        }
+    }
+
+    let is_observed_changed = {
+        let mut tabster_on_element = tabster_on_element.borrow_mut();
+        tabster_on_element.uncontrolled = new_tabster_props.uncontrolled.clone();
+        tabster_on_element.focusable = new_tabster_props.focusable.clone().map(Arc::new);
+
+        let names = |props: Option<&types::ObservedElementProps>| {
+            props.map(|observed| observed.names.clone())
+        };
+        let is_observed_changed = names(tabster_on_element.observed.as_ref())
+            != names(new_tabster_props.observed.as_ref());
+        tabster_on_element.observed = new_tabster_props.observed.clone();
+
+        is_observed_changed
+    };
+
     if let Some(new_attr) = new_attr {
         entry.attr = Some(new_attr);
     } else {
@@ -170,4 +287,20 @@ pub fn update_tabster_by_attribute(
         let mut tabster_ref = tabster.borrow_mut();
         tabster_ref.storage_entry(&element, Some(false));
     }
+    drop(entry);
+
+    if is_observed_changed {
+        let names = new_tabster_props
+            .observed
+            .as_ref()
+            .map(|observed| observed.names.clone());
+
+        if tabster.borrow().observed_element.is_some() {
+            ObservedElementAPI::on_observed_element_update(tabster, element, names);
+        } else if cfg!(debug_assertions) && names.is_some() {
+            console_error!(
+                "ObservedElement API used before initialization, please call `getObservedElement()`"
+            );
+        }
+    }
 }
@@ -1,5 +1,198 @@
-use web_sys::{wasm_bindgen::UnwrapThrowExt, HtmlElement};
+use web_sys::{
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    Event, HtmlElement, KeyboardEvent, MouseEvent, PointerEvent, Window,
+};
 
+use crate::{
+    consts::keys,
+    dom_api::DOM,
+    types::DOMAPI,
+    web::{add_event_listener_with_bool, EventListenerHandle},
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+thread_local! {
+    static IS_FOCUSING_PROGRAMMATICALLY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Focuses the element on behalf of Tabster, the focus events dispatched
+/// meanwhile are reported as programmatic.
 pub fn native_focus(element: HtmlElement) {
-    element.focus().unwrap_throw();
+    let was_focusing_programmatically = IS_FOCUSING_PROGRAMMATICALLY.replace(true);
+    let result = element.focus();
+    IS_FOCUSING_PROGRAMMATICALLY.set(was_focusing_programmatically);
+    result.unwrap_throw();
+}
+
+pub(crate) fn is_focusing_programmatically() -> bool {
+    IS_FOCUSING_PROGRAMMATICALLY.get()
+}
+
+pub type KeyborgCallback = Rc<dyn Fn(bool)>;
+
+/// Tracks whether the user is navigating with the keyboard or with a pointer.
+/// The keyboard mode is turned on by a key press and turned off by a mouse,
+/// touch or pen interaction.
+#[derive(Clone)]
+pub struct Keyborg {
+    win: Window,
+    is_navigating_with_keyboard: Rc<Cell<bool>>,
+    last_subscription_id: Rc<Cell<usize>>,
+    callbacks: Rc<RefCell<Vec<(usize, KeyborgCallback)>>>,
+    event_listener_handles: Rc<RefCell<Vec<EventListenerHandle>>>,
+}
+
+impl Keyborg {
+    pub fn new(win: Window) -> Self {
+        let this = Self {
+            win: win.clone(),
+            is_navigating_with_keyboard: Default::default(),
+            last_subscription_id: Default::default(),
+            callbacks: Default::default(),
+            event_listener_handles: Default::default(),
+        };
+
+        let handles = vec![
+            add_event_listener_with_bool(
+                win.clone(),
+                "keydown",
+                {
+                    let this = this.clone();
+                    move |event: KeyboardEvent| this.on_key_down(event)
+                },
+                true,
+            ),
+            add_event_listener_with_bool(
+                win.clone(),
+                "mousedown",
+                {
+                    let this = this.clone();
+                    move |event: MouseEvent| {
+                        if !is_screen_reader_click(&event) {
+                            this.set_val(false);
+                        }
+                    }
+                },
+                true,
+            ),
+            add_event_listener_with_bool(
+                win.clone(),
+                "pointerdown",
+                {
+                    let this = this.clone();
+                    move |event: PointerEvent| {
+                        // Keyboard activated clicks come without the pointer type.
+                        if !event.pointer_type().is_empty() && !is_screen_reader_click(&event) {
+                            this.set_val(false);
+                        }
+                    }
+                },
+                true,
+            ),
+            add_event_listener_with_bool(
+                win,
+                "touchstart",
+                {
+                    let this = this.clone();
+                    move |_: Event| this.set_val(false)
+                },
+                true,
+            ),
+        ];
+
+        *this.event_listener_handles.borrow_mut() = handles;
+
+        this
+    }
+
+    pub fn is_navigating_with_keyboard(&self) -> bool {
+        self.is_navigating_with_keyboard.get()
+    }
+
+    /// Sets the navigation mode, the subscribers are notified only when it changes.
+    pub fn set_val(&self, is_navigating_with_keyboard: bool) {
+        if self
+            .is_navigating_with_keyboard
+            .replace(is_navigating_with_keyboard)
+            == is_navigating_with_keyboard
+        {
+            return;
+        }
+
+        let callbacks: Vec<_> = self
+            .callbacks
+            .borrow()
+            .iter()
+            .map(|(_, callback)| callback.clone())
+            .collect();
+
+        for callback in callbacks {
+            callback(is_navigating_with_keyboard);
+        }
+    }
+
+    /// Returns the subscription id to pass to `unsubscribe()`.
+    pub fn subscribe(&self, callback: impl Fn(bool) + 'static) -> usize {
+        let id = self.last_subscription_id.get() + 1;
+        self.last_subscription_id.set(id);
+        self.callbacks.borrow_mut().push((id, Rc::new(callback)));
+        id
+    }
+
+    pub fn unsubscribe(&self, id: usize) {
+        self.callbacks
+            .borrow_mut()
+            .retain(|(subscription_id, _)| *subscription_id != id);
+    }
+
+    pub fn dispose(&self) {
+        for handle in self.event_listener_handles.borrow_mut().drain(..) {
+            handle.remove();
+        }
+
+        self.callbacks.borrow_mut().clear();
+    }
+
+    fn on_key_down(&self, event: KeyboardEvent) {
+        if self.is_navigating_with_keyboard() {
+            return;
+        }
+
+        if event.key() == keys::TAB {
+            self.set_val(true);
+            return;
+        }
+
+        // Typing is not navigating.
+        let is_editable = self
+            .win
+            .document()
+            .and_then(|doc| DOM::get_active_element(&doc))
+            .is_some_and(|active_element| {
+                let tag_name = active_element.tag_name();
+                tag_name == "INPUT"
+                    || tag_name == "TEXTAREA"
+                    || active_element
+                        .dyn_ref::<HtmlElement>()
+                        .is_some_and(|el| el.is_content_editable())
+            });
+
+        if !is_editable {
+            self.set_val(true);
+        }
+    }
+}
+
+/// A click with no buttons pressed or with all-zero coordinates is most likely
+/// triggered by a screen reader to perform an action on an element, it should
+/// not dismiss the keyboard navigation mode.
+fn is_screen_reader_click(event: &MouseEvent) -> bool {
+    event.buttons() == 0
+        || (event.client_x() == 0
+            && event.client_y() == 0
+            && event.screen_x() == 0
+            && event.screen_y() == 0)
 }
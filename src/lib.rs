@@ -1,13 +1,18 @@
 mod attribute_helpers;
 mod consts;
+mod deloser;
 mod dom_api;
 mod focusable;
 mod groupper;
 mod instance;
 mod keyborg;
+mod memory_dom;
 mod modalizer;
 mod mover;
 mod mutation_event;
+mod observed;
+mod outline;
+mod restorer;
 mod root;
 mod state;
 mod tabster;
@@ -17,5 +22,14 @@ mod web;
 
 pub use attribute_helpers::*;
 pub use consts::*;
+pub use dom_api::WebDOM;
 pub use focusable::FocusableAPI;
-pub use tabster::{create_tabster, get_groupper, get_mover, Tabster};
+pub use keyborg::Keyborg;
+pub use memory_dom::{MemoryDOM, MemoryElement};
+pub use observed::{ObservedElementAPI, ObservedElementAsyncRequest};
+pub use outline::OutlineProps;
+pub use tabster::{
+    create_tabster, get_deloser, get_groupper, get_modalizer, get_mover, get_observed_element,
+    get_outline, get_restorer, Tabster,
+};
+pub use utils::{create_element_tree_walker, get_last_child, ElementTreeWalker, FilterResult};
@@ -0,0 +1,236 @@
+use crate::types::{DOMBackend, TabsterDOMRect};
+use std::cell::{Cell, RefCell};
+
+/// An element of a `MemoryDOM`, only meaningful for the DOM which created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemoryElement(usize);
+
+struct MemoryNode {
+    tag_name: String,
+    attributes: Vec<(String, String)>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    rect: TabsterDOMRect,
+    display_none: bool,
+}
+
+/// `DOMBackend` without a browser. It knows nothing about styles, layout and
+/// shadow roots, the geometry and `display: none` are set by hand.
+pub struct MemoryDOM {
+    nodes: RefCell<Vec<MemoryNode>>,
+    active_element: Cell<Option<usize>>,
+}
+
+impl Default for MemoryDOM {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryDOM {
+    /// A document with an empty `<body>`.
+    pub fn new() -> Self {
+        let this = Self {
+            nodes: Default::default(),
+            active_element: Default::default(),
+        };
+        this.push_node("body", &[], None);
+
+        this
+    }
+
+    /// Creates an element as the last child of the parent.
+    pub fn append_element(
+        &self,
+        parent: &MemoryElement,
+        tag_name: &str,
+        attributes: &[(&str, &str)],
+    ) -> MemoryElement {
+        self.push_node(tag_name, attributes, Some(parent.0))
+    }
+
+    pub fn set_attribute(&self, element: &MemoryElement, name: &str, value: &str) {
+        let attributes = &mut self.nodes.borrow_mut()[element.0].attributes;
+
+        if let Some(attribute) = attributes.iter_mut().find(|(n, _)| n == name) {
+            attribute.1 = value.to_string();
+        } else {
+            attributes.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    pub fn remove_attribute(&self, element: &MemoryElement, name: &str) {
+        self.nodes.borrow_mut()[element.0]
+            .attributes
+            .retain(|(n, _)| n != name);
+    }
+
+    pub fn set_bounding_client_rect(&self, element: &MemoryElement, rect: TabsterDOMRect) {
+        self.nodes.borrow_mut()[element.0].rect = rect;
+    }
+
+    pub fn set_display_none(&self, element: &MemoryElement, display_none: bool) {
+        self.nodes.borrow_mut()[element.0].display_none = display_none;
+    }
+
+    fn push_node(
+        &self,
+        tag_name: &str,
+        attributes: &[(&str, &str)],
+        parent: Option<usize>,
+    ) -> MemoryElement {
+        let mut nodes = self.nodes.borrow_mut();
+        let index = nodes.len();
+
+        if let Some(parent) = parent {
+            nodes[parent].children.push(index);
+        }
+
+        nodes.push(MemoryNode {
+            tag_name: tag_name.to_uppercase(),
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            parent,
+            children: vec![],
+            // Everything is visible by default.
+            rect: TabsterDOMRect {
+                left: 0.0,
+                top: 0.0,
+                right: 10.0,
+                bottom: 10.0,
+            },
+            display_none: false,
+        });
+
+        MemoryElement(index)
+    }
+
+    /// The sibling at the offset from the element among its parent children.
+    fn sibling(&self, element: &MemoryElement, offset: isize) -> Option<MemoryElement> {
+        let nodes = self.nodes.borrow();
+        let siblings = &nodes[nodes[element.0].parent?].children;
+        let index = siblings.iter().position(|el| *el == element.0)?;
+
+        index
+            .checked_add_signed(offset)
+            .and_then(|index| siblings.get(index))
+            .map(|el| MemoryElement(*el))
+    }
+
+    fn tag_name(&self, element: &MemoryElement) -> String {
+        self.nodes.borrow()[element.0].tag_name.clone()
+    }
+
+    /// The part of `FOCUSABLE_SELECTOR` which doesn't depend on `tabindex`
+    /// and `contenteditable`.
+    fn is_natively_focusable(&self, element: &MemoryElement) -> bool {
+        match self.tag_name(element).as_str() {
+            "A" => self.has_attribute(element, "href"),
+            "BUTTON" | "INPUT" | "SELECT" | "TEXTAREA" => !self.has_attribute(element, "disabled"),
+            "SUMMARY" => self
+                .get_parent_element(element)
+                .is_some_and(|parent| self.tag_name(&parent) == "DETAILS"),
+            "AUDIO" | "VIDEO" => self.has_attribute(element, "controls"),
+            _ => false,
+        }
+    }
+}
+
+impl DOMBackend for MemoryDOM {
+    type Element = MemoryElement;
+
+    fn body(&self) -> Option<MemoryElement> {
+        Some(MemoryElement(0))
+    }
+
+    fn get_parent_element(&self, element: &MemoryElement) -> Option<MemoryElement> {
+        self.nodes.borrow()[element.0].parent.map(MemoryElement)
+    }
+
+    fn get_first_child(&self, element: &MemoryElement) -> Option<MemoryElement> {
+        self.nodes.borrow()[element.0]
+            .children
+            .first()
+            .map(|el| MemoryElement(*el))
+    }
+
+    fn get_last_child(&self, element: &MemoryElement) -> Option<MemoryElement> {
+        self.nodes.borrow()[element.0]
+            .children
+            .last()
+            .map(|el| MemoryElement(*el))
+    }
+
+    fn get_next_sibling(&self, element: &MemoryElement) -> Option<MemoryElement> {
+        self.sibling(element, 1)
+    }
+
+    fn get_previous_sibling(&self, element: &MemoryElement) -> Option<MemoryElement> {
+        self.sibling(element, -1)
+    }
+
+    fn get_active_element(&self) -> Option<MemoryElement> {
+        self.active_element.get().map(MemoryElement).or(self.body())
+    }
+
+    /// Like the browser, does nothing for an element which can't be focused.
+    fn focus(&self, element: &MemoryElement) {
+        if self.is_focusable(element, Some(true), None, Some(true)) {
+            self.active_element.set(Some(element.0));
+        }
+    }
+
+    fn get_attribute(&self, element: &MemoryElement, name: &str) -> Option<String> {
+        self.nodes.borrow()[element.0]
+            .attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    fn matches_focusable_selector(&self, element: &MemoryElement) -> bool {
+        self.has_attribute(element, "tabindex")
+            || self.has_attribute(element, "contenteditable")
+            || self.is_natively_focusable(element)
+    }
+
+    fn tab_index(&self, element: &MemoryElement) -> i32 {
+        if let Some(tab_index) = self
+            .get_attribute(element, "tabindex")
+            .and_then(|tab_index| tab_index.trim().parse().ok())
+        {
+            return tab_index;
+        }
+
+        if self.is_natively_focusable(element) || self.has_attribute(element, "contenteditable") {
+            0
+        } else {
+            -1
+        }
+    }
+
+    fn get_bounding_client_rect(&self, element: &MemoryElement) -> TabsterDOMRect {
+        if self.is_display_none(element) {
+            return TabsterDOMRect::default();
+        }
+
+        self.nodes.borrow()[element.0].rect
+    }
+
+    fn is_display_none(&self, element: &MemoryElement) -> bool {
+        let nodes = self.nodes.borrow();
+        let mut el = Some(element.0);
+
+        while let Some(el_ref) = el {
+            if nodes[el_ref].display_none {
+                return true;
+            }
+
+            el = nodes[el_ref].parent;
+        }
+
+        false
+    }
+}
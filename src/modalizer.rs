@@ -1,22 +1,41 @@
-use web_sys::{wasm_bindgen::UnwrapThrowExt, Element, HtmlElement};
+use web_sys::{
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    Element, HtmlElement, KeyboardEvent,
+};
 
 use crate::{
+    consts::keys,
     dom_api::DOM,
+    groupper::GroupperAPI,
+    instance::get_tabster_on_element,
+    keyborg::native_focus,
     root::RootAPI,
-    types::{self, DOMAPI},
-    utils::{NodeFilterEnum, TabsterPart},
+    tabster::TabsterCore,
+    types::{self, GetWindow, DOMAPI},
+    utils::{FilterResult, TabsterPart},
+    web::{add_event_listener_with_bool, set_timeout, EventListenerHandle},
 };
 use std::{
     cell::{RefCell, RefMut},
+    collections::HashMap,
     ops::Deref,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
 };
 
+static WAS_FOCUSED_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+const ARIA_HIDDEN: &str = "aria-hidden";
+
 pub type ArcCellModalizer = Arc<RefCell<Modalizer>>;
 
 pub struct Modalizer {
     part: TabsterPart<types::ModalizerProps>,
     pub user_id: String,
+    is_active: bool,
+    was_focused: u32,
 }
 
 impl Deref for Modalizer {
@@ -28,8 +47,52 @@ impl Deref for Modalizer {
 }
 
 impl Modalizer {
+    pub fn new(
+        tabster: Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+        props: types::ModalizerProps,
+    ) -> Arc<RefCell<Self>> {
+        Arc::new(RefCell::new(Self {
+            user_id: props.id.clone(),
+            part: TabsterPart::new(tabster, element.clone(), props),
+            is_active: false,
+            was_focused: 0,
+        }))
+    }
+
+    pub fn make_active(&mut self, is_active: bool) {
+        self.is_active = is_active;
+    }
+
+    pub fn focused(&mut self, no_increment: Option<bool>) -> u32 {
+        if !no_increment.unwrap_or_default() {
+            self.was_focused = WAS_FOCUSED_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+        }
+
+        self.was_focused
+    }
+
+    pub fn set_props(&mut self, props: types::ModalizerProps) {
+        if !props.id.is_empty() {
+            self.user_id = props.id.clone();
+        }
+
+        self.part.set_props(props);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    pub fn contains(&self, element: &HtmlElement) -> bool {
+        DOM::node_contains(
+            self.get_element().map(Into::into),
+            Some(element.clone().into()),
+        )
+    }
+
     pub(crate) fn find_next_tabbable(
-        &mut self,
+        &self,
         current_element: Option<HtmlElement>,
         reference_element: Option<HtmlElement>,
         is_backward: Option<bool>,
@@ -39,14 +102,10 @@ impl Modalizer {
             return None;
         }
 
-        let container = current_element
-            .clone()
-            .map(|current_element| {
-                RootAPI::get_root(&self.tabster, current_element)
-                    .map(|root| root.borrow().get_element())
-                    .flatten()
-            })
-            .flatten();
+        let container = current_element.clone().and_then(|current_element| {
+            RootAPI::get_root(&self.tabster, current_element)
+                .and_then(|root| root.borrow().get_element())
+        });
 
         let mut next = None::<HtmlElement>;
         let mut out_of_dom_order = false;
@@ -65,7 +124,7 @@ impl Modalizer {
 
             let tabster = self.tabster.borrow();
             let focusable = tabster.focusable.clone().unwrap_throw();
-            let mut focusable = focusable.borrow_mut();
+            let focusable = focusable.borrow();
             next = if is_backward.unwrap_or_default() {
                 focusable.find_prev(find_props, &mut find_props_out)
             } else {
@@ -73,7 +132,7 @@ impl Modalizer {
             };
 
             let active_id = if let Some(modalizer) = &tabster.modalizer {
-                modalizer.active_id.clone()
+                modalizer.borrow().active_id.clone()
             } else {
                 None
             };
@@ -111,20 +170,624 @@ impl Modalizer {
     }
 }
 
+struct AugmentedElement {
+    element: HtmlElement,
+    /// The `aria-hidden` value the element had before we hid it.
+    original: Option<String>,
+}
+
 pub struct ModalizerAPI {
+    tabster: Arc<RefCell<TabsterCore>>,
+    win: Arc<GetWindow>,
+    restore_modalizer_focus_timer: Arc<RefCell<Option<i32>>>,
+    modalizers: HashMap<String, ArcCellModalizer>,
+    parts: HashMap<String, HashMap<String, ArcCellModalizer>>,
+    aug: Vec<AugmentedElement>,
+    hidden_update_timer: Arc<RefCell<Option<i32>>>,
+    activation_history: Vec<Option<String>>,
+    event_listener_handle_keydown: Arc<RefCell<Option<EventListenerHandle>>>,
     pub active_id: Option<String>,
-    pub is_augmented: Box<dyn Fn(Element) -> bool>,
+    pub current_is_others_accessible: Option<bool>,
     pub active_elements: Vec<HtmlElement>,
 }
 
 impl ModalizerAPI {
+    pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
+        let event_listener_handle_keydown: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+
+        tabster.borrow_mut().queue_init({
+            let tabster = tabster.clone();
+            let get_window = get_window.clone();
+            let event_listener_handle_keydown = event_listener_handle_keydown.clone();
+            move || {
+                let win = get_window();
+
+                if let Some(focused_element) = tabster.borrow_mut().focused_element.as_mut() {
+                    let tabster = tabster.clone();
+                    focused_element.subscribe(move |_, element, detail| {
+                        if let Some(element) = element {
+                            Self::on_focus(&tabster, element, detail);
+                        }
+                    });
+                }
+
+                *event_listener_handle_keydown.borrow_mut() = Some(add_event_listener_with_bool(
+                    win,
+                    "keydown",
+                    move |event: KeyboardEvent| {
+                        Self::on_key_down(&tabster, event);
+                    },
+                    true,
+                ));
+            }
+        });
+
+        Self {
+            tabster,
+            win: get_window,
+            restore_modalizer_focus_timer: Default::default(),
+            modalizers: HashMap::new(),
+            parts: HashMap::new(),
+            aug: vec![],
+            hidden_update_timer: Default::default(),
+            activation_history: vec![],
+            event_listener_handle_keydown,
+            active_id: None,
+            current_is_others_accessible: None,
+            active_elements: vec![],
+        }
+    }
+
+    pub fn dispose(&mut self) {
+        if let Some(handle) = self.event_listener_handle_keydown.borrow_mut().take() {
+            handle.remove();
+        }
+
+        let win = (self.win)();
+
+        if let Some(timer) = self.restore_modalizer_focus_timer.borrow_mut().take() {
+            win.clear_timeout_with_handle(timer);
+        }
+
+        if let Some(timer) = self.hidden_update_timer.borrow_mut().take() {
+            win.clear_timeout_with_handle(timer);
+        }
+
+        // Giving the application its `aria-hidden` values back.
+        for aug in std::mem::take(&mut self.aug) {
+            restore_aria_hidden(aug);
+        }
+
+        self.modalizers.clear();
+        self.parts.clear();
+        self.activation_history.clear();
+        self.active_id = None;
+        self.current_is_others_accessible = None;
+        self.active_elements.clear();
+    }
+
+    pub fn create_modalizer(
+        &mut self,
+        element: &HtmlElement,
+        props: types::ModalizerProps,
+    ) -> ArcCellModalizer {
+        let modalizer = Modalizer::new(self.tabster.clone(), element, props);
+        let (id, user_id) = {
+            let modalizer = modalizer.borrow();
+            (modalizer.id.clone(), modalizer.user_id.clone())
+        };
+
+        self.modalizers.insert(id.clone(), modalizer.clone());
+        self.parts
+            .entry(user_id.clone())
+            .or_default()
+            .insert(id, modalizer.clone());
+
+        let focused_element = {
+            let tabster = self.tabster.borrow();
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        // Adding a modalizer which is already focused, activate it.
+        if let Some(focused_element) = focused_element {
+            if DOM::node_contains(Some(element.clone().into()), Some(focused_element.into())) {
+                if Some(&user_id) != self.active_id.as_ref() {
+                    self.set_active(Some(&modalizer));
+                } else {
+                    self.make_active(&modalizer, true);
+                }
+            }
+        }
+
+        modalizer
+    }
+
+    pub fn dispose_modalizer(&mut self, modalizer: &ArcCellModalizer) {
+        self.make_active(modalizer, false);
+
+        let (id, user_id) = {
+            let modalizer = modalizer.borrow();
+            (modalizer.id.clone(), modalizer.user_id.clone())
+        };
+
+        self.modalizers.remove(&id);
+
+        let Some(part) = self.parts.get_mut(&user_id) else {
+            return;
+        };
+
+        part.remove(&id);
+
+        if !part.is_empty() {
+            return;
+        }
+
+        self.parts.remove(&user_id);
+
+        let mut clean_activation_history = vec![];
+        let mut prev_history_item = None::<Option<String>>;
+
+        for modalizer_user_id_from_history in self.activation_history.iter().rev() {
+            if modalizer_user_id_from_history.as_ref() == Some(&user_id) {
+                continue;
+            }
+
+            if prev_history_item.as_ref() != Some(modalizer_user_id_from_history) {
+                prev_history_item = Some(modalizer_user_id_from_history.clone());
+
+                if modalizer_user_id_from_history.is_some() || !clean_activation_history.is_empty()
+                {
+                    clean_activation_history.insert(0, modalizer_user_id_from_history.clone());
+                }
+            }
+        }
+
+        self.activation_history = clean_activation_history;
+
+        if self.active_id.as_ref() == Some(&user_id) {
+            let prev_active = self
+                .activation_history
+                .first()
+                .cloned()
+                .flatten()
+                .and_then(|prev_active_id| self.parts.get(&prev_active_id))
+                .and_then(|part| part.values().next().cloned());
+
+            self.set_active(prev_active.as_ref());
+        }
+    }
+
+    pub fn is_augmented(&self, element: &Element) -> bool {
+        self.aug.iter().any(|aug| *aug.element == *element)
+    }
+
+    pub fn set_active(&mut self, modalizer: Option<&ArcCellModalizer>) {
+        let user_id = modalizer.map(|modalizer| modalizer.borrow().user_id.clone());
+        let active_id = self.active_id.clone();
+
+        if active_id == user_id {
+            return;
+        }
+
+        self.active_id = user_id.clone();
+
+        if let Some(part) = active_id.and_then(|active_id| self.parts.get(&active_id).cloned()) {
+            for modalizer in part.values() {
+                self.make_active(modalizer, false);
+            }
+        }
+
+        if let Some(part) = user_id
+            .as_ref()
+            .and_then(|user_id| self.parts.get(user_id).cloned())
+        {
+            for modalizer in part.values() {
+                self.make_active(modalizer, true);
+            }
+        }
+
+        self.current_is_others_accessible =
+            modalizer.and_then(|modalizer| modalizer.borrow().get_props().is_others_accessible);
+
+        self.hidden_update();
+
+        if self.activation_history.first() != Some(&user_id)
+            && (user_id.is_some() || !self.activation_history.is_empty())
+        {
+            self.activation_history.insert(0, user_id);
+        }
+    }
+
+    fn make_active(&mut self, modalizer: &ArcCellModalizer, is_active: bool) {
+        let mut modalizer = modalizer.borrow_mut();
+
+        if modalizer.is_active() == is_active {
+            return;
+        }
+
+        modalizer.make_active(is_active);
+
+        if let Some(element) = modalizer.get_element() {
+            let index = self.active_elements.iter().position(|el| *el == element);
+
+            if is_active {
+                if index.is_none() {
+                    self.active_elements.push(element);
+                }
+            } else if let Some(index) = index {
+                self.active_elements.remove(index);
+            }
+        } else if !is_active {
+            // The element has left the document already.
+            self.active_elements.retain(|el| el.is_connected());
+        }
+    }
+
+    /// Schedules the `aria-hidden` update of everything outside of the active modalizer.
+    pub fn hidden_update(&mut self) {
+        if self.hidden_update_timer.borrow().is_some() {
+            return;
+        }
+
+        let hidden_update_timer = self.hidden_update_timer.clone();
+        let tabster = self.tabster.clone();
+
+        let timer = set_timeout(
+            &(self.win)(),
+            move || {
+                *hidden_update_timer.borrow_mut() = None;
+
+                let modalizer = tabster.borrow().modalizer.clone();
+                if let Some(modalizer) = modalizer {
+                    modalizer.borrow_mut().hidden_update_now();
+                }
+            },
+            250,
+        );
+
+        *self.hidden_update_timer.borrow_mut() = Some(timer);
+    }
+
+    fn hidden_update_now(&mut self) {
+        let Some(body) = (self.win)().document().and_then(|doc| doc.body()) else {
+            return;
+        };
+
+        let active_id = self.active_id.clone();
+        let mut visible_elements = vec![];
+        let mut hidden_elements = vec![];
+        let mut always_accessible_elements = vec![];
+
+        for (user_id, modalizer_parts) in self.parts.iter() {
+            for modalizer in modalizer_parts.values() {
+                let modalizer = modalizer.borrow();
+                let Some(el) = modalizer.get_element() else {
+                    continue;
+                };
+
+                if Some(user_id) == active_id.as_ref() {
+                    if !self.current_is_others_accessible.unwrap_or_default() {
+                        visible_elements.push(el);
+                    }
+                } else if modalizer
+                    .get_props()
+                    .is_always_accessible
+                    .unwrap_or_default()
+                {
+                    always_accessible_elements.push(el);
+                } else {
+                    hidden_elements.push(el);
+                }
+            }
+        }
+
+        let mut old_aug = std::mem::take(&mut self.aug);
+        let mut new_aug = vec![];
+
+        let mut toggle = |element: &HtmlElement, hide: bool| {
+            let old_index = old_aug.iter().position(|aug| aug.element == *element);
+
+            if hide {
+                if new_aug
+                    .iter()
+                    .any(|aug: &AugmentedElement| aug.element == *element)
+                {
+                    return;
+                }
+
+                let original = if let Some(old_index) = old_index {
+                    old_aug.remove(old_index).original
+                } else {
+                    let original = element.get_attribute(ARIA_HIDDEN);
+
+                    if original.as_deref() == Some("true") {
+                        // Already hidden by the application, leaving it alone.
+                        return;
+                    }
+
+                    element.set_attribute(ARIA_HIDDEN, "true").unwrap_throw();
+                    original
+                };
+
+                new_aug.push(AugmentedElement {
+                    element: element.clone(),
+                    original,
+                });
+            } else if let Some(old_index) = old_index {
+                restore_aria_hidden(old_aug.remove(old_index));
+            }
+        };
+
+        if visible_elements.is_empty() {
+            for el in always_accessible_elements.iter() {
+                toggle(el, false);
+            }
+
+            for el in hidden_elements.iter() {
+                toggle(el, true);
+            }
+        } else {
+            let mut all_visible_elements = visible_elements;
+            all_visible_elements.append(&mut always_accessible_elements);
+
+            walk(&body, &all_visible_elements, &mut toggle);
+        }
+
+        for aug in old_aug {
+            restore_aria_hidden(aug);
+        }
+
+        self.aug = new_aug;
+    }
+
+    fn on_focus(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        focused_element: HtmlElement,
+        detail: &types::FocusedElementDetail,
+    ) {
+        let Some(ctx) = RootAPI::get_tabster_context(tabster, &focused_element, Default::default())
+        else {
+            return;
+        };
+
+        let Some(api) = tabster.borrow().modalizer.clone() else {
+            return;
+        };
+
+        let mut api_ref = api.borrow_mut();
+
+        // If the newly focused element is inside some of the hidden containers,
+        // remove aria-hidden from those synchronously.
+        let mut e = Some(focused_element.clone());
+        while let Some(el) = e {
+            if let Some(index) = api_ref.aug.iter().position(|aug| aug.element == el) {
+                restore_aria_hidden(api_ref.aug.remove(index));
+            }
+
+            e = DOM::get_parent_element(Some(el));
+        }
+
+        let modalizer = ctx.modalizer;
+
+        if let Some(modalizer) = modalizer.as_ref() {
+            modalizer.borrow_mut().focused(None);
+        }
+
+        let user_id = modalizer
+            .as_ref()
+            .map(|modalizer| modalizer.borrow().user_id.clone());
+
+        if user_id == api_ref.active_id {
+            api_ref.current_is_others_accessible = modalizer
+                .as_ref()
+                .and_then(|modalizer| modalizer.borrow().get_props().is_others_accessible);
+            return;
+        }
+
+        let is_always_accessible = modalizer
+            .as_ref()
+            .and_then(|modalizer| modalizer.borrow().get_props().is_always_accessible)
+            .unwrap_or_default();
+
+        // An inactive groupper with the modalizer on the same node will not give the modalizer
+        // the focus while it is inactive.
+        if detail.is_focused_programmatically()
+            || api_ref.active_id.is_none()
+            || api_ref.current_is_others_accessible.unwrap_or_default()
+            || is_always_accessible
+        {
+            api_ref.set_active(modalizer.as_ref());
+        } else {
+            // Focused outside of the active modalizer, try pull focus back to current modalizer.
+            let win = (api_ref.win)();
+
+            if let Some(timer) = api_ref.restore_modalizer_focus_timer.borrow_mut().take() {
+                win.clear_timeout_with_handle(timer);
+            }
+
+            let restore_modalizer_focus_timer = api_ref.restore_modalizer_focus_timer.clone();
+            let tabster = tabster.clone();
+
+            // Some rendering frameworks might rerender the DOM asynchronously, giving them
+            // a chance to settle before pulling the focus back.
+            let timer = set_timeout(
+                &win,
+                move || {
+                    *restore_modalizer_focus_timer.borrow_mut() = None;
+                    Self::restore_modalizer_focus(&tabster);
+                },
+                100,
+            );
+
+            *api_ref.restore_modalizer_focus_timer.borrow_mut() = Some(timer);
+        }
+    }
+
+    fn restore_modalizer_focus(tabster: &Arc<RefCell<TabsterCore>>) {
+        let focused_element = {
+            let tabster = tabster.borrow();
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        let Some(focused_element) = focused_element else {
+            return;
+        };
+
+        let Some(api) = tabster.borrow().modalizer.clone() else {
+            return;
+        };
+
+        let ctx = RootAPI::get_tabster_context(tabster, &focused_element, Default::default());
+        let modalizer = ctx.and_then(|ctx| ctx.modalizer);
+        let user_id = modalizer
+            .as_ref()
+            .map(|modalizer| modalizer.borrow().user_id.clone());
+
+        let active_elements = {
+            let api = api.borrow();
+
+            if user_id == api.active_id {
+                return;
+            }
+
+            api.active_id
+                .as_ref()
+                .and_then(|active_id| api.parts.get(active_id))
+                .map(|part| {
+                    part.values()
+                        .filter_map(|modalizer| {
+                            let modalizer = modalizer.borrow();
+                            let props = modalizer.get_props();
+                            modalizer.get_element().map(|element| {
+                                (
+                                    element,
+                                    props.is_no_focus_default.unwrap_or_default(),
+                                    props.is_no_focus_first.unwrap_or_default(),
+                                )
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        let focusable = tabster.borrow().focusable.clone().unwrap_throw();
+
+        for (container, is_no_focus_default, is_no_focus_first) in active_elements {
+            let mut to_focus = None;
+
+            if !is_no_focus_default {
+                to_focus = focusable.borrow().find_default(
+                    types::FindDefaultProps {
+                        container: container.clone(),
+                        modalizer_id: None,
+                        include_programmatically_focusable: None,
+                        use_active_modalizer: Some(true),
+                        ignore_accessibility: None,
+                    },
+                    &mut Default::default(),
+                );
+            }
+
+            if to_focus.is_none() && !is_no_focus_first {
+                to_focus = focusable.borrow().find_first(
+                    types::FindFirstProps {
+                        container,
+                        ignore_accessibility: None,
+                        use_active_modalizer: Some(true),
+                    },
+                    &mut Default::default(),
+                );
+            }
+
+            if let Some(to_focus) = to_focus {
+                native_focus(to_focus);
+                return;
+            }
+        }
+
+        // Nothing to focus in the active modalizer (it might be gone already),
+        // the focused element's modalizer wins.
+        api.borrow_mut().set_active(modalizer.as_ref());
+    }
+
+    fn on_key_down(tabster: &Arc<RefCell<TabsterCore>>, event: KeyboardEvent) {
+        if event.key() != keys::ESCAPE {
+            return;
+        }
+
+        let element = {
+            let tabster = tabster.borrow();
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        let Some(element) = element else {
+            return;
+        };
+
+        let Some(ctx) = RootAPI::get_tabster_context(tabster, &element, Default::default()) else {
+            return;
+        };
+
+        let Some(modalizer) = ctx.modalizer.as_ref() else {
+            return;
+        };
+
+        if ctx.groupper.is_some()
+            || !modalizer.borrow().is_active()
+            || (ctx.ignore_keydown)(event.clone())
+        {
+            return;
+        }
+
+        let active_id = modalizer.borrow().user_id.clone();
+        let Some(api) = tabster.borrow().modalizer.clone() else {
+            return;
+        };
+
+        let part = api.borrow().parts.get(&active_id).cloned();
+        let Some(part) = part else {
+            return;
+        };
+
+        let mut focused_since = part
+            .values()
+            .filter_map(|modalizer| {
+                let mut modalizer = modalizer.borrow_mut();
+                let el = modalizer.get_element()?;
+                get_tabster_on_element(tabster, &el)?
+                    .borrow()
+                    .groupper
+                    .as_ref()?;
+                Some((el, modalizer.focused(Some(true))))
+            })
+            .filter(|(_, focused_since)| *focused_since > 0)
+            .collect::<Vec<_>>();
+
+        focused_since.sort_by_key(|(_, focused_since)| std::cmp::Reverse(*focused_since));
+
+        if let Some((groupper_element, _)) = focused_since.into_iter().next() {
+            GroupperAPI::handle_key_press(tabster, groupper_element, event, Some(true));
+        }
+    }
+
     pub fn accept_element(
         &self,
         element: &Element,
         state: &mut RefMut<'_, types::FocusableAcceptElementState>,
-    ) -> Option<u32> {
+    ) -> Option<FilterResult> {
         let modalizer_user_id = state.modalizer_user_id.clone();
-        let current_modalizer = state.current_ctx.clone().map(|c| c.modalizer).flatten();
+        let current_modalizer = state.current_ctx.clone().and_then(|c| c.modalizer);
 
         if modalizer_user_id.is_some() {
             for el in self.active_elements.iter() {
@@ -133,7 +796,7 @@ impl ModalizerAPI {
                 {
                     // We have a part of currently active modalizer somewhere deeper in the DOM,
                     // skipping all other checks.
-                    return Some(*NodeFilterEnum::FilterSkip);
+                    return Some(FilterResult::Skip);
                 }
             }
         }
@@ -157,7 +820,58 @@ impl ModalizerAPI {
             None
         } else {
             state.skipped_focusable = Some(true);
-            Some(*NodeFilterEnum::FilterSkip)
+            Some(FilterResult::Skip)
+        }
+    }
+}
+
+fn restore_aria_hidden(aug: AugmentedElement) {
+    if let Some(original) = aug.original {
+        aug.element
+            .set_attribute(ARIA_HIDDEN, &original)
+            .unwrap_throw();
+    } else {
+        aug.element.remove_attribute(ARIA_HIDDEN).unwrap_throw();
+    }
+}
+
+/// Hides every branch of the tree that doesn't contain any of the visible elements.
+fn walk(
+    element: &Element,
+    all_visible_elements: &[HtmlElement],
+    toggle: &mut impl FnMut(&HtmlElement, bool),
+) {
+    let mut child = element.first_element_child();
+
+    while let Some(el) = child {
+        child = el.next_element_sibling();
+
+        let Ok(el) = el.dyn_into::<HtmlElement>() else {
+            continue;
+        };
+
+        let el_parent = el.parent_node();
+        let mut skip = false;
+        let mut contains_modalizer = false;
+        let mut contained_by_modalizer = false;
+
+        for c in all_visible_elements {
+            if el == *c {
+                skip = true;
+                break;
+            }
+
+            if el.contains(Some(c)) {
+                contains_modalizer = true;
+            } else if c.contains(el_parent.as_ref()) {
+                contained_by_modalizer = true;
+            }
+        }
+
+        if contains_modalizer {
+            walk(&el, all_visible_elements, toggle);
+        } else if !skip && !contained_by_modalizer {
+            toggle(&el, true);
         }
     }
 }
@@ -1,29 +1,44 @@
 use crate::{
     console_log,
-    dom_api::DOM,
+    dom_api::{WebDOM, DOM},
     instance::get_tabster_on_element,
-    state::focused_element::FOCUSED_ELEMENT_STATE_IS_TABBING,
+    keyborg::native_focus,
+    keys, mover_directions, mover_keys,
+    root::RootAPI,
+    state::focused_element::{FocusedElementState, FOCUSED_ELEMENT_STATE_IS_TABBING},
     tabster::TabsterCore,
-    types::{self, GetWindow, MoverProps, DOMAPI},
+    types::{
+        self, DOMBackend, GetTabsterContextOptions, GetWindow, MoverProps, TabsterDOMRect, DOMAPI,
+    },
     utils::{
-        get_dummy_input_container, get_element_uid, DummyInputManager, NodeFilterEnum, TabsterPart,
-        WeakHTMLElement,
+        create_element_tree_walker, get_dummy_input_container, get_element_uid,
+        get_scrollable_container, is_element_vertically_visible_in_container, matches_selector,
+        should_ignore_focus, DummyInputManager, FilterResult, TabsterPart, WeakHTMLElement,
     },
     visibilities,
-    web::{add_event_listener_with_bool, set_timeout, EventListenerHandle},
+    web::{
+        add_event_listener, add_event_listener_with_bool, dispatch_custom_event, set_timeout,
+        EventListenerHandle,
+    },
+    MOVER_MEMORIZED_ELEMENT_EVENT_NAME, MOVER_MOVE_FOCUS_EVENT_NAME, MOVER_STATE_EVENT_NAME,
 };
 use std::{
     cell::{RefCell, RefMut},
     collections::HashMap,
     ops::{Deref, DerefMut},
+    rc::Rc,
     sync::{atomic::Ordering, Arc},
 };
 use web_sys::{
+    js_sys::Reflect,
     wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt},
-    Element, HtmlElement, IntersectionObserver, IntersectionObserverEntry,
-    IntersectionObserverInit,
+    CustomEvent, Element, HtmlElement, HtmlInputElement, HtmlTextAreaElement, IntersectionObserver,
+    IntersectionObserverEntry, IntersectionObserverInit, KeyboardEvent, MutationObserver,
+    MutationObserverInit,
 };
 
+const INPUT_SELECTOR: &str = "input, textarea, *[contenteditable]";
+
 struct MoverDummyManager(DummyInputManager);
 
 impl MoverDummyManager {
@@ -32,12 +47,56 @@ impl MoverDummyManager {
         tabster: Arc<RefCell<TabsterCore>>,
         sys: Option<types::SysProps>,
     ) -> Self {
-        Self(DummyInputManager::new(tabster, element, sys, None))
+        let mut dummy_input_manager =
+            DummyInputManager::new(tabster.clone(), element.clone(), sys, None);
+        dummy_input_manager.set_handlers(
+            Some(Box::new(move |dummy_input, is_backward, _| {
+                let (Some(container), Some(input)) = (element.get(), dummy_input.input()) else {
+                    return;
+                };
+
+                let mut to_focus =
+                    RootAPI::get_tabster_context(&tabster, &input, Default::default())
+                        .and_then(|ctx| {
+                            FocusedElementState::find_next_tabbable(
+                                &tabster,
+                                ctx,
+                                None,
+                                Some(input),
+                                None,
+                                Some(is_backward),
+                                Some(true),
+                            )
+                        })
+                        .and_then(|next_tabbable| next_tabbable.element);
+
+                let memorized = Mover::mover_on_element(&tabster, &container)
+                    .map(|mover| mover.borrow().memorized_candidates())
+                    .unwrap_or_default();
+                let focusable = tabster.borrow().focusable.clone();
+
+                if let Some(memorized) = memorized.into_iter().find(|el| {
+                    focusable
+                        .as_ref()
+                        .is_some_and(|f| f.borrow().is_focusable(el, None, None, None))
+                }) {
+                    to_focus = Some(memorized);
+                }
+
+                if let Some(to_focus) = to_focus {
+                    native_focus(to_focus);
+                }
+            })),
+            None,
+        );
+        Self(dummy_input_manager)
     }
 }
 
 pub type ArcCellMover = Arc<RefCell<Mover>>;
 
+pub type MoverStateCallback = Rc<dyn Fn(&HtmlElement, &types::MoverElementState)>;
+
 pub struct Mover {
     part: TabsterPart<types::MoverProps>,
 
@@ -45,8 +104,17 @@ pub struct Mover {
     intersection_observer: Option<IntersectionObserver>,
     set_current_timer: Option<i32>,
     current: Option<RefCell<WeakHTMLElement>>,
+    /// The focusables next to and before the memorized current element, to
+    /// fall back to when it is removed.
+    current_neighbours: Vec<WeakHTMLElement>,
     visible: Arc<RefCell<HashMap<String, types::Visibility>>>,
     fully_visible: Arc<RefCell<Option<String>>>,
+    mutation_observer: Option<MutationObserver>,
+    update_elements_timer: Option<i32>,
+    /// The tracked focusable elements (with their uids) in the document order.
+    elements: Vec<(String, WeakHTMLElement)>,
+    /// The states last reported to the `MoverAPI` subscribers.
+    states: HashMap<String, types::MoverElementState>,
 
     dummy_manager: Option<MoverDummyManager>,
     visibility_tolerance: f32,
@@ -78,8 +146,6 @@ impl Mover {
         let visibility_tolerance = props.visibility_tolerance.unwrap_or(0.8);
 
         // this._onDispose = onDispose;
-        // const getMemorized = () =>
-        //     props.memorizeCurrent ? this._current : undefined;
 
         let win = { tabster.borrow().get_window.clone() };
 
@@ -91,8 +157,13 @@ impl Mover {
             visibility_tolerance,
             set_current_timer: None,
             current: None,
+            current_neighbours: vec![],
             visible: Default::default(),
             fully_visible: Default::default(),
+            mutation_observer: None,
+            update_elements_timer: None,
+            elements: vec![],
+            states: HashMap::new(),
         }
         .init(tabster, props, sys)
     }
@@ -109,50 +180,88 @@ impl Mover {
         };
 
         self.dummy_manager = if !control_tab {
-            Some(MoverDummyManager::new(
-                self._element.clone(),
-                tabster,
-                // getMemorized,
-                sys,
-            ))
+            Some(MoverDummyManager::new(self._element.clone(), tabster, sys))
         } else {
             None
         };
 
         if props.track_state.unwrap_or_default() || props.visibility_aware.unwrap_or_default() > 0 {
-            let win = self.win.clone();
-            let on_intersection: Closure<dyn Fn(Vec<IntersectionObserverEntry>)> =
-                Closure::new(move |entries: Vec<IntersectionObserverEntry>| {
-                    for entry in entries.into_iter() {
-                        let el = entry.target().dyn_into::<HtmlElement>().unwrap_throw();
-                        let id = get_element_uid(&win, &el);
-                    }
-                });
-            let on_intersection = on_intersection.into_js_value();
-            let options = IntersectionObserverInit::new();
-            let threshold =
-                serde_wasm_bindgen::to_value(&[0.0, 0.25, 0.5, 0.75, 1.0]).unwrap_throw();
-            options.set_threshold(&threshold);
-            self.intersection_observer = Some(
-                IntersectionObserver::new_with_options(
-                    on_intersection.as_ref().unchecked_ref(),
-                    &options,
-                )
-                .unwrap_throw(),
-            );
-
+            // The root is corrected once the elements are collected.
+            self.intersection_observer = Some(self.create_intersection_observer(None));
             self.observe_state();
         }
 
         self
     }
 
+    fn create_intersection_observer(&self, root: Option<&Element>) -> IntersectionObserver {
+        let on_intersection: Closure<dyn Fn(Vec<IntersectionObserverEntry>)> = {
+            let tabster = self.tabster.clone();
+            let mover_element = self._element.clone();
+            Closure::new(move |entries: Vec<IntersectionObserverEntry>| {
+                Self::on_intersection(&tabster, &mover_element, entries);
+            })
+        };
+        let on_intersection = on_intersection.into_js_value();
+        let options = IntersectionObserverInit::new();
+        let threshold =
+            serde_wasm_bindgen::to_value(&[0.0, 0.25, 0.5, 0.75, self.visibility_tolerance, 1.0])
+                .unwrap_throw();
+        options.set_threshold(&threshold);
+        options.set_root(root);
+
+        IntersectionObserver::new_with_options(on_intersection.as_ref().unchecked_ref(), &options)
+            .unwrap_throw()
+    }
+
+    /// The nearest scroll container of the Mover elements, `None` for the
+    /// viewport.
+    fn get_intersection_root(&self) -> Option<Element> {
+        let first = self.elements.iter().find_map(|(_, el)| el.get())?;
+        let container = get_scrollable_container(&first)?;
+
+        if first.owner_document()?.document_element() == Some(container.clone().into()) {
+            None
+        } else {
+            Some(container.into())
+        }
+    }
+
     pub fn id(&self) -> &String {
         &self.part.id
     }
 
+    pub fn dispose(&mut self) {
+        if let Some(intersection_observer) = self.intersection_observer.take() {
+            intersection_observer.disconnect();
+        }
+
+        if let Some(mutation_observer) = self.mutation_observer.take() {
+            mutation_observer.disconnect();
+        }
+
+        if let Some(timer) = self.set_current_timer.take() {
+            (self.win)().clear_timeout_with_handle(timer);
+        }
+
+        if let Some(timer) = self.update_elements_timer.take() {
+            (self.win)().clear_timeout_with_handle(timer);
+        }
+
+        if let Some(mut dummy_manager) = self.dummy_manager.take() {
+            dummy_manager.0.dispose();
+        }
+
+        self.current = None;
+        self.current_neighbours.clear();
+        self.visible.borrow_mut().clear();
+        *self.fully_visible.borrow_mut() = None;
+        self.elements.clear();
+        self.states.clear();
+    }
+
     pub(crate) fn find_next_tabbable(
-        &mut self,
+        &self,
         current_element: Option<HtmlElement>,
         reference_element: Option<HtmlElement>,
         is_backward: Option<bool>,
@@ -187,14 +296,14 @@ impl Mover {
 
             let tabster = self.tabster.borrow();
             let focusable = tabster.focusable.clone().unwrap_throw();
-            let mut focusable = focusable.borrow_mut();
+            let focusable = focusable.borrow();
             next = if is_backward.unwrap_or_default() {
                 focusable.find_prev(find_props, &mut find_props_out)
             } else {
                 focusable.find_next(find_props, &mut find_props_out)
             };
 
-            out_of_dom_order = find_props_out.out_of_dom_order.unwrap_throw();
+            out_of_dom_order = find_props_out.out_of_dom_order.unwrap_or_default();
 
             uncontrolled = find_props_out.uncontrolled;
         }
@@ -210,14 +319,14 @@ impl Mover {
         &self,
         element: &Element,
         state: &mut RefMut<'_, types::FocusableAcceptElementState>,
-    ) -> Option<u32> {
+    ) -> Option<FilterResult> {
         if !FOCUSED_ELEMENT_STATE_IS_TABBING.load(Ordering::SeqCst) {
             let Some(current_ctx) = state.current_ctx.as_ref() else {
                 return None;
             };
 
             if current_ctx.excluded_from_mover.unwrap() {
-                return Some(*NodeFilterEnum::FilterReject);
+                return Some(FilterResult::Reject);
             } else {
                 return None;
             }
@@ -245,17 +354,10 @@ impl Mover {
             let mut found: Option<HtmlElement> = None;
 
             if memorize_current.unwrap_or_default() {
-                let current = if let Some(current) = &self.current {
-                    current.borrow_mut().get()
-                } else {
-                    None
-                };
-
-                if let Some(current) = current {
-                    if (state.accept_condition)(current.clone()) {
-                        found = Some(current);
-                    }
-                }
+                found = self
+                    .memorized_candidates()
+                    .into_iter()
+                    .find(|el| (state.accept_condition)(el.clone()));
             }
 
             if found.is_none() && has_default {
@@ -265,7 +367,7 @@ impl Mover {
                     .focusable
                     .clone()
                     .map(|f| {
-                        f.borrow_mut().find_default(
+                        f.borrow().find_default(
                             types::FindDefaultProps {
                                 container: mover_element.clone().unwrap_throw(),
                                 modalizer_id: None,
@@ -286,11 +388,13 @@ impl Mover {
                     .focusable
                     .clone()
                     .map(|f| {
+                        let focusable = f.clone();
                         let win = self.win.clone();
                         let visible = self.visible.clone();
                         let mover_element = mover_element.clone().unwrap_throw();
                         let fully_visible = self.fully_visible.clone();
-                        f.borrow_mut().find_element(
+                        let ignore_accessibility = state.ignore_accessibility;
+                        f.borrow().find_element(
                             types::FindFocusableProps {
                                 container: mover_element.clone(),
                                 current_element: None,
@@ -304,6 +408,7 @@ impl Mover {
                                     let id = get_element_uid(&win, &el);
                                     let visibility = visible.borrow().get(&id).cloned();
 
+                                    // Only the tracked elements have the visibility.
                                     mover_element != el
                                         && (visibility == Some(visibilities::VISIBLE)
                                             || (visibility
@@ -311,16 +416,12 @@ impl Mover {
                                                 && (visibility_aware
                                                     == Some(visibilities::PARTIALLY_VISIBLE)
                                                     || fully_visible.borrow().is_none())))
-                                    // return (
-                                    //     moverElement !== el &&
-                                    //     !!this._allElements?.get(el) &&
-                                    //     state.acceptCondition(el) &&
-                                    //     (visibility === Visibilities.Visible ||
-                                    //         (visibility === Visibilities.PartiallyVisible &&
-                                    //             (visibilityAware ===
-                                    //                 Visibilities.PartiallyVisible ||
-                                    //                 !this._fullyVisible)))
-                                    // );
+                                        && focusable.borrow().is_focusable(
+                                            &el,
+                                            None,
+                                            Some(false),
+                                            ignore_accessibility,
+                                        )
                                 })),
                                 on_element: None,
                             },
@@ -335,19 +436,383 @@ impl Mover {
                 state.found_element = Some(found);
                 state.reject_elements_from = mover_element;
                 state.skipped_focusable = Some(true);
-                return Some(*NodeFilterEnum::FilterAccept);
+                return Some(FilterResult::Accept);
             }
         }
 
         None
     }
 
-    fn observe_state(&self) {
-        let element = self.get_element();
+    fn mover_on_element(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+    ) -> Option<ArcCellMover> {
+        get_tabster_on_element(tabster, element).and_then(|t| t.borrow().mover.clone())
+    }
 
-        if element.is_none() {
+    fn observe_state(&mut self) {
+        if self.get_element().is_none() {
             return;
         }
+
+        let observer = DOM::create_mutation_observer({
+            let tabster = self.tabster.clone();
+            let mover_element = self._element.clone();
+            move |_, _| {
+                let Some(mover) = mover_element
+                    .get()
+                    .and_then(|el| Self::mover_on_element(&tabster, &el))
+                else {
+                    return;
+                };
+
+                mover.borrow_mut().schedule_update_elements();
+            }
+        });
+
+        let init = MutationObserverInit::new();
+        init.set_child_list(true);
+        init.set_subtree(true);
+        init.set_attributes(true);
+        let attribute_filter =
+            serde_wasm_bindgen::to_value(&["tabindex", "disabled", "aria-hidden"]).unwrap_throw();
+        init.set_attribute_filter(&attribute_filter);
+        observer
+            .observe_with_options(&self.get_element().unwrap_throw(), &init)
+            .unwrap_throw();
+        self.mutation_observer = Some(observer);
+
+        // The Mover is not in the storage yet, the elements are collected
+        // once it is.
+        self.schedule_update_elements();
+    }
+
+    fn schedule_update_elements(&mut self) {
+        if self.update_elements_timer.is_some() {
+            return;
+        }
+
+        let tabster = self.tabster.clone();
+        let mover_element = self._element.clone();
+
+        self.update_elements_timer = set_timeout(
+            &(self.win)(),
+            move || {
+                if let Some(mover_element) = mover_element.get() {
+                    Self::update_elements(&tabster, &mover_element);
+                }
+            },
+            0,
+        )
+        .into();
+    }
+
+    /// Collects the focusable elements of the Mover (the nested Movers are
+    /// tracked by themselves, only their own element belongs to this Mover).
+    fn collect_elements(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        mover_element: &HtmlElement,
+    ) -> Vec<HtmlElement> {
+        let Some(focusable) = tabster.borrow().focusable.clone() else {
+            return vec![];
+        };
+        let elements = Rc::new(RefCell::new(vec![]));
+
+        let Some(doc) = mover_element.owner_document() else {
+            return vec![];
+        };
+        let dom = WebDOM::new(doc);
+        let mut walker = create_element_tree_walker(&dom, mover_element, {
+            let tabster = tabster.clone();
+            let mover_element = mover_element.clone();
+            let elements = elements.clone();
+            move |el: &Element| {
+                let Some(el) = el.dyn_ref::<HtmlElement>().cloned() else {
+                    return FilterResult::Skip;
+                };
+
+                if el == mover_element || should_ignore_focus(&el) {
+                    return FilterResult::Skip;
+                }
+
+                if focusable.borrow().is_focusable(&el, None, None, None) {
+                    elements.borrow_mut().push(el.clone());
+                }
+
+                if Self::mover_on_element(&tabster, &el).is_some() {
+                    FilterResult::Reject
+                } else {
+                    FilterResult::Skip
+                }
+            }
+        });
+
+        walker.next_node();
+
+        elements.take()
+    }
+
+    fn update_elements(tabster: &Arc<RefCell<TabsterCore>>, mover_element: &HtmlElement) {
+        let Some(mover) = Self::mover_on_element(tabster, mover_element) else {
+            return;
+        };
+
+        mover.borrow_mut().update_elements_timer = None;
+
+        let elements = Self::collect_elements(tabster, mover_element);
+
+        {
+            let mut mover = mover.borrow_mut();
+            let win = mover.win.clone();
+            let elements: Vec<_> = elements
+                .into_iter()
+                .map(|el| (get_element_uid(&win, &el), el))
+                .collect();
+            let new_ids: HashMap<_, _> = elements.iter().cloned().collect();
+            let mut old_ids: HashMap<_, _> = mover.elements.drain(..).collect();
+            mover.elements = elements
+                .iter()
+                .map(|(id, el)| {
+                    (
+                        id.clone(),
+                        WeakHTMLElement::new(win.clone(), el.clone(), None),
+                    )
+                })
+                .collect();
+            let removed: Vec<_> = old_ids
+                .iter()
+                .filter(|(id, _)| !new_ids.contains_key(*id))
+                .map(|(id, el)| (id.clone(), el.get()))
+                .collect();
+
+            // The visibility is relative to the scroll container, which might
+            // be different once the elements are changed. The elements which
+            // have left the document can't be unobserved, the observer is
+            // started over too.
+            let root = mover.get_intersection_root();
+            if mover
+                .intersection_observer
+                .as_ref()
+                .is_some_and(|intersection_observer| {
+                    intersection_observer.root() != root
+                        || removed.iter().any(|(_, el)| el.is_none())
+                })
+            {
+                if let Some(intersection_observer) = mover.intersection_observer.take() {
+                    intersection_observer.disconnect();
+                }
+
+                mover.intersection_observer =
+                    Some(mover.create_intersection_observer(root.as_ref()));
+                old_ids.clear();
+                mover.visible.borrow_mut().clear();
+                *mover.fully_visible.borrow_mut() = None;
+            }
+
+            for (id, el) in elements.iter() {
+                if !old_ids.contains_key(id) {
+                    if let Some(intersection_observer) = &mover.intersection_observer {
+                        intersection_observer.observe(el);
+                    }
+                }
+            }
+
+            for (id, el) in removed.iter() {
+                if let (Some(intersection_observer), Some(el)) = (&mover.intersection_observer, el)
+                {
+                    intersection_observer.unobserve(el);
+                }
+
+                mover.visible.borrow_mut().remove(id);
+
+                if mover.fully_visible.borrow().as_ref() == Some(id) {
+                    *mover.fully_visible.borrow_mut() = None;
+                }
+            }
+        }
+
+        Self::update_states(tabster, &mover);
+    }
+
+    fn on_intersection(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        mover_element: &WeakHTMLElement,
+        entries: Vec<IntersectionObserverEntry>,
+    ) {
+        let Some(mover) = mover_element
+            .get()
+            .and_then(|el| Self::mover_on_element(tabster, &el))
+        else {
+            return;
+        };
+
+        {
+            let mover = mover.borrow();
+            let mut visible = mover.visible.borrow_mut();
+            let mut fully_visible = mover.fully_visible.borrow_mut();
+
+            for entry in entries.into_iter() {
+                let el = entry.target().dyn_into::<HtmlElement>().unwrap_throw();
+                let id = get_element_uid(&mover.win, &el);
+                let ratio = entry.intersection_ratio();
+
+                let visibility = if ratio >= mover.visibility_tolerance as f64 {
+                    visibilities::VISIBLE
+                } else if entry.is_intersecting() && ratio > 0.0 {
+                    visibilities::PARTIALLY_VISIBLE
+                } else {
+                    visibilities::INVISIBLE
+                };
+
+                if visibility == visibilities::VISIBLE {
+                    *fully_visible = Some(id.clone());
+                } else if fully_visible.as_ref() == Some(&id) {
+                    *fully_visible = None;
+                }
+
+                visible.insert(id, visibility);
+            }
+        }
+
+        Self::update_states(tabster, &mover);
+    }
+
+    fn compute_states(&self) -> Vec<(&String, HtmlElement, types::MoverElementState)> {
+        let elements: Vec<_> = self
+            .elements
+            .iter()
+            .filter_map(|(id, el)| Some((id, el.get()?)))
+            .collect();
+        let count = elements.len();
+        let current = self
+            .current
+            .as_ref()
+            .and_then(|current| current.borrow().get());
+        let current_index =
+            current.and_then(|current| elements.iter().position(|(_, el)| *el == current));
+        let visible = self.visible.borrow();
+
+        elements
+            .into_iter()
+            .enumerate()
+            .map(|(index, (id, el))| {
+                let state = types::MoverElementState {
+                    is_current: current_index == Some(index),
+                    is_previous: current_index == Some(index + 1),
+                    is_next: current_index.is_some_and(|current| index == current + 1),
+                    is_first: index == 0,
+                    is_last: index + 1 == count,
+                    visibility: visible.get(id).cloned().unwrap_or(visibilities::INVISIBLE),
+                    index,
+                    count,
+                };
+
+                (id, el, state)
+            })
+            .collect()
+    }
+
+    /// The state of a focusable element of the Mover, `None` when the element
+    /// is not tracked by this Mover.
+    pub fn get_state(&self, element: &HtmlElement) -> Option<types::MoverElementState> {
+        self.compute_states()
+            .into_iter()
+            .find(|(_, el, _)| el == element)
+            .map(|(_, _, state)| state)
+    }
+
+    /// Reports the changed states to the `MoverAPI` subscribers.
+    fn update_states(tabster: &Arc<RefCell<TabsterCore>>, mover: &ArcCellMover) {
+        let changed: Vec<_> = {
+            let mut mover = mover.borrow_mut();
+
+            if !mover.props.track_state.unwrap_or_default() {
+                return;
+            }
+
+            let states: HashMap<_, _> = mover
+                .compute_states()
+                .into_iter()
+                .map(|(id, el, state)| (id.clone(), (el, state)))
+                .collect();
+
+            let changed = states
+                .iter()
+                .filter(|(id, (_, state))| mover.states.get(*id) != Some(state))
+                .map(|(_, (el, state))| (el.clone(), *state))
+                .collect();
+
+            mover.states = states
+                .into_iter()
+                .map(|(id, (_, state))| (id, state))
+                .collect();
+
+            changed
+        };
+
+        if !changed.is_empty() {
+            MoverAPI::notify_state(tabster, changed);
+        }
+    }
+
+    /// The memorized current element, or its former neighbours when it is not
+    /// in the DOM anymore.
+    fn memorized_candidates(&self) -> Vec<HtmlElement> {
+        if !self.props.memorize_current.unwrap_or_default() {
+            return vec![];
+        }
+
+        if let Some(current) = self.current.as_ref().and_then(|c| c.borrow().get()) {
+            return vec![current];
+        }
+
+        self.current_neighbours
+            .iter()
+            .filter_map(|neighbour| neighbour.get())
+            .collect()
+    }
+
+    fn update_current_neighbours(tabster: &Arc<RefCell<TabsterCore>>, mover: &ArcCellMover) {
+        let (container, current) = {
+            let mover = mover.borrow();
+
+            if !mover.props.memorize_current.unwrap_or_default() {
+                return;
+            }
+
+            let current = mover.current.as_ref().and_then(|c| c.borrow().get());
+            let (Some(container), Some(current)) = (mover.get_element(), current) else {
+                return;
+            };
+
+            (container, current)
+        };
+
+        let Some(focusable) = tabster.borrow().focusable.clone() else {
+            return;
+        };
+
+        let find_props = || types::FindNextProps {
+            current_element: Some(current.clone()),
+            reference_element: None,
+            container: container.clone(),
+            ignore_accessibility: None,
+            use_active_modalizer: Some(true),
+        };
+        let next = focusable
+            .borrow()
+            .find_next(find_props(), &mut Default::default());
+        let prev = focusable
+            .borrow()
+            .find_prev(find_props(), &mut Default::default());
+
+        let mut mover = mover.borrow_mut();
+        let win = mover.win.clone();
+        mover.current_neighbours = next
+            .into_iter()
+            .chain(prev)
+            .map(|el| WeakHTMLElement::new(win.clone(), el, None))
+            .collect();
     }
 
     fn set_current(&mut self, element: Option<HtmlElement>) {
@@ -355,15 +820,30 @@ impl Mover {
             self.current = Some(WeakHTMLElement::new(self.win.clone(), element, None).into());
         } else {
             self.current = None;
+            self.current_neighbours.clear();
         }
 
-        if self.props.track_state.unwrap_or_default()
-            || self.props.visibility_aware.unwrap_or_default() != 0
+        if (self.props.memorize_current.unwrap_or_default()
+            || self.props.track_state.unwrap_or_default()
+            || self.props.visibility_aware.unwrap_or_default() != 0)
+            && self.set_current_timer.is_none()
         {
+            let tabster = self.tabster.clone();
+            let mover_element = self._element.clone();
+
             self.set_current_timer = set_timeout(
                 &(self.win)(),
                 move || {
-                    // TODO
+                    let Some(mover) = mover_element
+                        .get()
+                        .and_then(|el| Self::mover_on_element(&tabster, &el))
+                    else {
+                        return;
+                    };
+
+                    mover.borrow_mut().set_current_timer = None;
+                    Self::update_current_neighbours(&tabster, &mover);
+                    Self::update_states(&tabster, &mover);
                 },
                 0,
             )
@@ -372,38 +852,102 @@ impl Mover {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn get_distance(
+    ax1: f64,
+    ay1: f64,
+    ax2: f64,
+    ay2: f64,
+    bx1: f64,
+    by1: f64,
+    bx2: f64,
+    by2: f64,
+) -> f64 {
+    let x_distance = if ax2 < bx1 {
+        bx1 - ax2
+    } else if bx2 < ax1 {
+        ax1 - bx2
+    } else {
+        0.0
+    };
+    let y_distance = if ay2 < by1 {
+        by1 - ay2
+    } else if by2 < ay1 {
+        ay1 - by2
+    } else {
+        0.0
+    };
+
+    if x_distance == 0.0 {
+        y_distance
+    } else if y_distance == 0.0 {
+        x_distance
+    } else {
+        (x_distance * x_distance + y_distance * y_distance).sqrt()
+    }
+}
+
 pub struct MoverAPI {
     tabster: Arc<RefCell<TabsterCore>>,
     win: Arc<GetWindow>,
     movers: HashMap<String, Arc<RefCell<Mover>>>,
     event_listener_handle_keydown: Arc<RefCell<Option<EventListenerHandle>>>,
+    event_listener_handle_move_focus: Arc<RefCell<Option<EventListenerHandle>>>,
+    event_listener_handle_memorized_element: Arc<RefCell<Option<EventListenerHandle>>>,
+    last_state_subscription_id: usize,
+    state_callbacks: Vec<(usize, MoverStateCallback)>,
 }
 
 impl MoverAPI {
     pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
         let event_listener_handle_keydown: Arc<RefCell<Option<EventListenerHandle>>> =
             Default::default();
+        let event_listener_handle_move_focus: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+        let event_listener_handle_memorized_element: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
 
         tabster.borrow_mut().queue_init({
             let tabster = tabster.clone();
             let get_window = get_window.clone();
             let event_listener_handle_keydown = event_listener_handle_keydown.clone();
+            let event_listener_handle_move_focus = event_listener_handle_move_focus.clone();
+            let event_listener_handle_memorized_element =
+                event_listener_handle_memorized_element.clone();
             move || {
                 let win = get_window();
 
                 *event_listener_handle_keydown.borrow_mut() = Some(add_event_listener_with_bool(
-                    win,
+                    win.clone(),
                     "keydown",
-                    move |_: web_sys::KeyboardEvent| {
-                        // this._onKeyDown
+                    {
+                        let tabster = tabster.clone();
+                        move |event: KeyboardEvent| {
+                            Self::on_key_down(&tabster, event);
+                        }
                     },
                     true,
                 ));
-                // win.addEventListener(MoverMoveFocusEventName, this._onMoveFocus);
-                // win.addEventListener(
-                //     MoverMemorizedElementEventName,
-                //     this._onMemorizedElement
-                // );
+                *event_listener_handle_move_focus.borrow_mut() = Some(add_event_listener(
+                    win.clone(),
+                    MOVER_MOVE_FOCUS_EVENT_NAME,
+                    {
+                        let tabster = tabster.clone();
+                        move |event: CustomEvent| {
+                            Self::on_move_focus(&tabster, event);
+                        }
+                    },
+                ));
+                *event_listener_handle_memorized_element.borrow_mut() = Some(add_event_listener(
+                    win,
+                    MOVER_MEMORIZED_ELEMENT_EVENT_NAME,
+                    {
+                        let tabster = tabster.clone();
+                        move |event: CustomEvent| {
+                            Self::on_memorized_element(&tabster, event);
+                        }
+                    },
+                ));
 
                 let on_focus = {
                     let tabster = tabster.clone();
@@ -448,7 +992,11 @@ impl MoverAPI {
                 };
 
                 if let Some(focused_element) = tabster.borrow_mut().focused_element.as_mut() {
-                    focused_element.subscribe(on_focus);
+                    focused_element.subscribe(move |_, element, _| {
+                        if let Some(element) = element {
+                            on_focus(element);
+                        }
+                    });
                 }
             }
         });
@@ -457,13 +1005,577 @@ impl MoverAPI {
             win: get_window,
             movers: HashMap::new(),
             event_listener_handle_keydown,
+            event_listener_handle_move_focus,
+            event_listener_handle_memorized_element,
+            last_state_subscription_id: 0,
+            state_callbacks: vec![],
         }
     }
 
-    fn dispose(self) {
+    pub fn dispose(&mut self) {
         if let Some(handle) = self.event_listener_handle_keydown.borrow_mut().take() {
             handle.remove();
         }
+
+        if let Some(handle) = self.event_listener_handle_move_focus.borrow_mut().take() {
+            handle.remove();
+        }
+
+        if let Some(handle) = self
+            .event_listener_handle_memorized_element
+            .borrow_mut()
+            .take()
+        {
+            handle.remove();
+        }
+
+        self.movers.clear();
+        self.state_callbacks.clear();
+    }
+
+    /// The state of the element in its closest Mover with `track_state`.
+    pub fn get_state(&self, element: &HtmlElement) -> Option<types::MoverElementState> {
+        let mut el = DOM::get_parent_element(Some(element.clone()));
+
+        while let Some(el_ref) = el {
+            if let Some(mover) = Mover::mover_on_element(&self.tabster, &el_ref) {
+                let mover = mover.borrow();
+
+                return if mover.props.track_state.unwrap_or_default() {
+                    mover.get_state(element)
+                } else {
+                    None
+                };
+            }
+
+            el = DOM::get_parent_element(Some(el_ref));
+        }
+
+        None
+    }
+
+    /// Subscribes to the state changes of the elements inside the Movers with
+    /// `track_state`. Returns the subscription id to pass to `unsubscribe_state()`.
+    pub fn subscribe_state(
+        &mut self,
+        callback: impl Fn(&HtmlElement, &types::MoverElementState) + 'static,
+    ) -> usize {
+        self.last_state_subscription_id += 1;
+        let id = self.last_state_subscription_id;
+        self.state_callbacks.push((id, Rc::new(callback)));
+        id
+    }
+
+    pub fn unsubscribe_state(&mut self, id: usize) {
+        self.state_callbacks
+            .retain(|(subscription_id, _)| *subscription_id != id);
+    }
+
+    fn notify_state(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        changed: Vec<(HtmlElement, types::MoverElementState)>,
+    ) {
+        let Some(mover_api) = tabster.borrow().mover.clone() else {
+            return;
+        };
+
+        let callbacks: Vec<_> = mover_api
+            .borrow()
+            .state_callbacks
+            .iter()
+            .map(|(_, callback)| callback.clone())
+            .collect();
+
+        for (element, state) in changed.iter() {
+            for callback in callbacks.iter() {
+                callback(element, state);
+            }
+
+            dispatch_custom_event(element, MOVER_STATE_EVENT_NAME, state);
+        }
+    }
+
+    fn on_key_down(tabster: &Arc<RefCell<TabsterCore>>, event: KeyboardEvent) {
+        // Give a chance to other listeners to handle the event (for example,
+        // to scroll instead of moving focus).
+        if event.ctrl_key() || event.alt_key() || event.shift_key() || event.meta_key() {
+            return;
+        }
+
+        let key = event.key();
+        let mover_key = match key.as_str() {
+            keys::ARROW_DOWN => mover_keys::ARROW_DOWN,
+            keys::ARROW_RIGHT => mover_keys::ARROW_RIGHT,
+            keys::ARROW_UP => mover_keys::ARROW_UP,
+            keys::ARROW_LEFT => mover_keys::ARROW_LEFT,
+            keys::PAGE_DOWN => mover_keys::PAGE_DOWN,
+            keys::PAGE_UP => mover_keys::PAGE_UP,
+            keys::HOME => mover_keys::HOME,
+            keys::END => mover_keys::END,
+            _ => return,
+        };
+
+        let focused = {
+            let tabster = tabster.borrow();
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        let Some(focused) = focused else {
+            return;
+        };
+
+        if Self::is_ignored_input(&focused, &key) {
+            return;
+        }
+
+        Self::move_focus(tabster, focused, mover_key, Some(event));
+    }
+
+    fn on_move_focus(tabster: &Arc<RefCell<TabsterCore>>, event: CustomEvent) {
+        let element = event.composed_path().get(0).dyn_into::<HtmlElement>().ok();
+        let detail =
+            serde_wasm_bindgen::from_value::<types::MoverMoveFocusEventDetail>(event.detail());
+
+        if let (Some(element), Ok(detail)) = (element, detail) {
+            if !event.default_prevented() {
+                Self::move_focus(tabster, element, detail.key, None);
+                event.stop_immediate_propagation();
+            }
+        }
+    }
+
+    fn on_memorized_element(tabster: &Arc<RefCell<TabsterCore>>, event: CustomEvent) {
+        // The target is retargeted to the shadow host outside of the shadow
+        // root, the path starts with the element the event is dispatched on.
+        let Ok(target) = event.composed_path().get(0).dyn_into::<HtmlElement>() else {
+            return;
+        };
+
+        let Some(mover) = RootAPI::get_tabster_context(tabster, &target, Default::default())
+            .and_then(|ctx| ctx.mover)
+        else {
+            return;
+        };
+
+        let mover_element = mover.borrow().get_element();
+        let memorized_element = Reflect::get(&event.detail(), &"memorizedElement".into())
+            .ok()
+            .and_then(|el| el.dyn_into::<HtmlElement>().ok())
+            .filter(|el| {
+                DOM::node_contains(
+                    mover_element.clone().map(Into::into),
+                    Some(el.clone().into()),
+                )
+            });
+
+        mover.borrow_mut().set_current(memorized_element);
+        event.stop_immediate_propagation();
+    }
+
+    pub(crate) fn move_focus(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        from_element: HtmlElement,
+        key: types::MoverKey,
+        related_event: Option<KeyboardEvent>,
+    ) -> Option<HtmlElement> {
+        let ctx = RootAPI::get_tabster_context(
+            tabster,
+            &from_element,
+            GetTabsterContextOptions {
+                check_rtl: Some(true),
+                reference_element: None,
+            },
+        )?;
+
+        let mover = ctx.mover.clone()?;
+
+        if ctx.excluded_from_mover.unwrap_or_default() {
+            return None;
+        }
+
+        if let Some(related_event) = &related_event {
+            if (ctx.ignore_keydown)(related_event.clone()) {
+                return None;
+            }
+        }
+
+        let container = mover.borrow().get_element();
+
+        if ctx.groupper_before_mover.unwrap_or_default() {
+            let groupper = ctx.groupper.clone();
+            if let Some(groupper) =
+                groupper.filter(|g| !g.borrow().is_active(Some(true)).unwrap_or_default())
+            {
+                // For the cases when we have Mover/Active Groupper/Inactive Groupper, we need to check
+                // the grouppers between the current element and the current mover.
+                let mut el = DOM::get_parent_element(groupper.borrow().get_element());
+                while let Some(e) = el {
+                    if Some(&e) == container.as_ref() {
+                        break;
+                    }
+
+                    let parent_groupper = get_tabster_on_element(tabster, &e)
+                        .and_then(|t| t.borrow().groupper.clone());
+                    if let Some(parent_groupper) = parent_groupper {
+                        if parent_groupper
+                            .borrow_mut()
+                            .is_active(Some(true))
+                            .unwrap_or_default()
+                        {
+                            return None;
+                        }
+                    }
+
+                    el = DOM::get_parent_element(Some(e));
+                }
+            } else {
+                return None;
+            }
+        }
+
+        let container = container?;
+
+        let focusable = tabster.borrow().focusable.clone().unwrap_throw();
+        let focusable = focusable.borrow();
+        let (direction, is_cyclic, visibility_tolerance) = {
+            let mover = mover.borrow();
+            let props = mover.get_props();
+            (
+                props.direction.unwrap_or(mover_directions::BOTH),
+                props.cyclic.unwrap_or_default(),
+                mover.visibility_tolerance,
+            )
+        };
+        let is_both = direction == mover_directions::BOTH;
+        let is_vertical = is_both || direction == mover_directions::VERTICAL;
+        let is_horizontal = is_both || direction == mover_directions::HORIZONTAL;
+        let is_grid_linear = direction == mover_directions::GRID_LINEAR;
+        let is_grid = is_grid_linear || direction == mover_directions::GRID;
+
+        let mut key = key;
+        let next = Rc::new(RefCell::new(None::<HtmlElement>));
+        let mut scroll_into_view_arg = None::<bool>;
+        let mut focused_element_rect = None::<TabsterDOMRect>;
+        let mut focused_element_x1 = 0.0;
+        let mut focused_element_x2 = 0.0;
+        let dom = WebDOM::new(from_element.owner_document()?);
+
+        if is_grid {
+            let rect = dom.get_bounding_client_rect(&from_element);
+            focused_element_x1 = rect.left.ceil();
+            focused_element_x2 = rect.right.floor();
+            focused_element_rect = Some(rect);
+        }
+
+        if ctx.rtl.unwrap_or_default() {
+            if key == mover_keys::ARROW_RIGHT {
+                key = mover_keys::ARROW_LEFT;
+            } else if key == mover_keys::ARROW_LEFT {
+                key = mover_keys::ARROW_RIGHT;
+            }
+        }
+
+        let find_next_props = |current_element: Option<HtmlElement>| types::FindNextProps {
+            current_element,
+            reference_element: None,
+            container: container.clone(),
+            ignore_accessibility: None,
+            use_active_modalizer: Some(true),
+        };
+        let find_first_props = || types::FindFirstProps {
+            container: container.clone(),
+            ignore_accessibility: None,
+            use_active_modalizer: Some(true),
+        };
+        // Walks from `current_element` remembering the elements rejected by
+        // `condition` as the next candidate, until `condition` accepts one,
+        // which is returned.
+        let find_while = |current_element: HtmlElement,
+                          is_backward: bool,
+                          condition: Box<dyn Fn(&HtmlElement) -> bool>| {
+            focusable.find_element(
+                types::FindFocusableProps {
+                    is_backward: Some(is_backward),
+                    accept_condition: Some(Box::new({
+                        let focusable = focusable.clone();
+                        let next = next.clone();
+                        move |el| {
+                            if !focusable.is_focusable(&el, None, None, None) {
+                                return false;
+                            }
+
+                            if condition(&el) {
+                                return true;
+                            }
+
+                            *next.borrow_mut() = Some(el);
+                            false
+                        }
+                    })),
+                    ..find_next_props(Some(current_element)).into()
+                },
+                &mut Default::default(),
+            )
+        };
+
+        if (key == mover_keys::ARROW_DOWN && is_vertical)
+            || (key == mover_keys::ARROW_RIGHT && (is_horizontal || is_grid))
+        {
+            let mut found = focusable.find_next(
+                find_next_props(Some(from_element.clone())),
+                &mut Default::default(),
+            );
+
+            if let Some(el) = found.as_ref().filter(|_| is_grid) {
+                let next_element_x1 = dom.get_bounding_client_rect(el).left.ceil();
+
+                if !is_grid_linear && focused_element_x2 > next_element_x1 {
+                    found = None;
+                }
+            } else if found.is_none() && is_cyclic {
+                found = focusable.find_first(find_first_props(), &mut Default::default());
+            }
+
+            *next.borrow_mut() = found;
+        } else if (key == mover_keys::ARROW_UP && is_vertical)
+            || (key == mover_keys::ARROW_LEFT && (is_horizontal || is_grid))
+        {
+            let mut found = focusable.find_prev(
+                find_next_props(Some(from_element.clone())),
+                &mut Default::default(),
+            );
+
+            if let Some(el) = found.as_ref().filter(|_| is_grid) {
+                let next_element_x2 = dom.get_bounding_client_rect(el).right.floor();
+
+                if !is_grid_linear && next_element_x2 > focused_element_x1 {
+                    found = None;
+                }
+            } else if found.is_none() && is_cyclic {
+                found = focusable.find_last(find_first_props(), &mut Default::default());
+            }
+
+            *next.borrow_mut() = found;
+        } else if key == mover_keys::HOME {
+            if is_grid {
+                let from = from_element.clone();
+                let dom = dom.clone();
+                find_while(
+                    from_element.clone(),
+                    true,
+                    Box::new(move |el| {
+                        let next_element_x1 = dom.get_bounding_client_rect(el).left.ceil();
+                        *el != from && focused_element_x1 <= next_element_x1
+                    }),
+                );
+            } else {
+                *next.borrow_mut() =
+                    focusable.find_first(find_first_props(), &mut Default::default());
+            }
+        } else if key == mover_keys::END {
+            if is_grid {
+                let from = from_element.clone();
+                let dom = dom.clone();
+                find_while(
+                    from_element.clone(),
+                    false,
+                    Box::new(move |el| {
+                        let next_element_x1 = dom.get_bounding_client_rect(el).left.ceil();
+                        *el != from && focused_element_x1 >= next_element_x1
+                    }),
+                );
+            } else {
+                *next.borrow_mut() =
+                    focusable.find_last(find_first_props(), &mut Default::default());
+            }
+        } else if key == mover_keys::PAGE_UP || key == mover_keys::PAGE_DOWN {
+            let is_backward = key == mover_keys::PAGE_UP;
+
+            let first_invisible = find_while(
+                from_element.clone(),
+                is_backward,
+                Box::new(move |el| {
+                    !is_element_vertically_visible_in_container(el, visibility_tolerance)
+                }),
+            );
+
+            // will be on the first (or the last) visible item
+            let edge = next.borrow().clone();
+
+            if edge.is_none() {
+                // The focused element is the last (or the first) visible one
+                // already, paging to the next invisible one to scroll it into view.
+                *next.borrow_mut() = first_invisible;
+            }
+
+            if let Some(edge) = edge.filter(|_| is_grid) {
+                let edge_column_x1 = dom.get_bounding_client_rect(&edge).left.ceil();
+                let dom = dom.clone();
+
+                find_while(
+                    edge,
+                    !is_backward,
+                    Box::new(move |el| {
+                        let next_element_x1 = dom.get_bounding_client_rect(el).left.ceil();
+                        if is_backward {
+                            focused_element_x1 < next_element_x1
+                                || edge_column_x1 >= next_element_x1
+                        } else {
+                            focused_element_x1 > next_element_x1
+                                || edge_column_x1 <= next_element_x1
+                        }
+                    }),
+                );
+            }
+
+            scroll_into_view_arg = Some(!is_backward);
+        } else if let Some(focused_element_rect) = focused_element_rect {
+            let is_backward = key == mover_keys::ARROW_UP;
+            let ax1 = focused_element_x1;
+            let ay1 = focused_element_rect.top.ceil();
+            let ax2 = focused_element_x2;
+            let ay2 = focused_element_rect.bottom.floor();
+            let last_distance = Rc::new(RefCell::new(None::<f64>));
+            let last_intersection = Rc::new(RefCell::new(0.0));
+
+            focusable.find_all(
+                types::FindAllProps {
+                    container: container.clone(),
+                    current_element: Some(from_element.clone()),
+                    is_backward: Some(is_backward),
+                    use_active_modalizer: None,
+                    on_element: Some(Box::new({
+                        let next = next.clone();
+                        let dom = dom.clone();
+                        move |el| {
+                            // Find element which has maximal intersection with the focused element horizontally,
+                            // or the closest one.
+                            let rect = dom.get_bounding_client_rect(&el);
+                            let bx1 = rect.left.ceil();
+                            let by1 = rect.top.ceil();
+                            let bx2 = rect.right.floor();
+                            let by2 = rect.bottom.floor();
+
+                            if (is_backward && ay1 < by2) || (!is_backward && ay2 > by1) {
+                                // Only look at the elements in the direction of the focused element.
+                                return true;
+                            }
+
+                            let x_intersection_width =
+                                f64::min(ax2, bx2).ceil() - f64::max(ax1, bx1).floor();
+                            let min_width = f64::min(ax2 - ax1, bx2 - bx1).ceil();
+                            let mut last_intersection = last_intersection.borrow_mut();
+
+                            if x_intersection_width > 0.0 && min_width >= x_intersection_width {
+                                // Element intersects with the focused element on X axis.
+                                let intersection = x_intersection_width / min_width;
+
+                                if intersection > *last_intersection {
+                                    *next.borrow_mut() = Some(el);
+                                    *last_intersection = intersection;
+                                }
+                            } else if *last_intersection == 0.0 {
+                                // If we didn't have intersection, try just the closest one.
+                                let distance = get_distance(ax1, ay1, ax2, ay2, bx1, by1, bx2, by2);
+                                let mut last_distance = last_distance.borrow_mut();
+
+                                if last_distance.is_none_or(|last| distance < last) {
+                                    *last_distance = Some(distance);
+                                    *next.borrow_mut() = Some(el);
+                                }
+                            } else if *last_intersection > 0.0 {
+                                // Element doesn't intersect, but we had intersection already, stop search.
+                                return false;
+                            }
+
+                            true
+                        }
+                    })),
+                },
+                &mut Default::default(),
+            );
+        }
+
+        let next = next.take()?;
+
+        if let Some(scroll_into_view_arg) = scroll_into_view_arg {
+            if !is_element_vertically_visible_in_container(&next, visibility_tolerance) {
+                next.scroll_into_view_with_bool(scroll_into_view_arg);
+            }
+        }
+
+        if let Some(related_event) = related_event {
+            related_event.prevent_default();
+            related_event.stop_immediate_propagation();
+        }
+
+        native_focus(next.clone());
+
+        Some(next)
+    }
+
+    /// Inputs, textareas and contenteditables use the arrow keys themselves
+    /// as long as the caret is not at the edge of the text.
+    fn is_ignored_input(element: &HtmlElement, key: &str) -> bool {
+        if element.get_attribute("aria-expanded").as_deref() == Some("true")
+            && element.has_attribute("aria-activedescendant")
+        {
+            // It is likely a combobox with expanded options and arrow keys are controlled by it.
+            return true;
+        }
+
+        if !matches_selector(element, INPUT_SELECTOR) {
+            return false;
+        }
+
+        let (selection_start, selection_end, text_length) =
+            if let Some(input) = element.dyn_ref::<HtmlInputElement>() {
+                let Some(selection_start) = input.selection_start().ok().flatten() else {
+                    // Do not ignore not text editable inputs like checkboxes and radios (but ignore hidden).
+                    return input.type_() == "hidden";
+                };
+                let selection_end = input.selection_end().ok().flatten().unwrap_or_default();
+                (
+                    selection_start,
+                    selection_end,
+                    input.value().encode_utf16().count() as u32,
+                )
+            } else if let Some(textarea) = element.dyn_ref::<HtmlTextAreaElement>() {
+                (
+                    textarea
+                        .selection_start()
+                        .ok()
+                        .flatten()
+                        .unwrap_or_default(),
+                    textarea.selection_end().ok().flatten().unwrap_or_default(),
+                    textarea.value().encode_utf16().count() as u32,
+                )
+            } else {
+                // The caret position of a contenteditable cannot be compared
+                // to the text length synchronously, leaving the keys to it.
+                return element.is_content_editable();
+            };
+
+        if selection_start != selection_end {
+            return true;
+        }
+
+        if selection_start > 0
+            && (key == keys::ARROW_LEFT || key == keys::ARROW_UP || key == keys::HOME)
+        {
+            return true;
+        }
+
+        if selection_start < text_length
+            && (key == keys::ARROW_RIGHT || key == keys::ARROW_DOWN || key == keys::END)
+        {
+            return true;
+        }
+
+        false
     }
 
     pub fn create_mover(
@@ -3,16 +3,17 @@ use std::{cell::RefCell, sync::Arc};
 use crate::{
     console_log,
     consts::TABSTER_ATTRIBUTE_NAME,
-    dom_api::DOM,
+    dom_api::{WebDOM, DOM},
     instance::{get_tabster_on_element, update_tabster_by_attribute},
+    observed::ObservedElementAPI,
     tabster::TabsterCore,
     types::{GetWindow, DOMAPI},
-    utils::{create_element_tree_walker, get_instance_context, NodeFilterEnum},
+    utils::{create_element_tree_walker, get_instance_context, FilterResult},
 };
 use web_sys::{
     js_sys::Set,
     wasm_bindgen::{JsCast, UnwrapThrowExt},
-    Document, Element, HtmlElement, MutationObserverInit, MutationRecord, Node,
+    Document, Element, HtmlElement, MutationObserver, MutationObserverInit, MutationRecord, Node,
 };
 
 pub fn observe_mutations(
@@ -29,12 +30,11 @@ pub fn observe_mutations(
     let element_by_uid = Arc::new(RefCell::new(None::<String>));
 
     let on_mutation = {
-        let doc = doc.clone();
         let tabster = tabster.clone();
         let get_window = get_window.clone();
         let element_by_uid = element_by_uid.clone();
 
-        move |mutations: Vec<MutationRecord>, _| {
+        move |mutations: Vec<MutationRecord>, observer: MutationObserver| {
             console_log!("fn observe_mutations on_mutation");
             let removed_nodes = Set::default();
 
@@ -72,7 +72,7 @@ pub fn observe_mutations(
                         update_tabster_elements(
                             tabster.clone(),
                             &get_window,
-                            &doc,
+                            &observer,
                             element_by_uid.clone(),
                             removed_node,
                             Some(true),
@@ -85,7 +85,7 @@ pub fn observe_mutations(
                         update_tabster_elements(
                             tabster.clone(),
                             &get_window,
-                            &doc,
+                            &observer,
                             element_by_uid.clone(),
                             added_node,
                             None,
@@ -95,28 +95,36 @@ pub fn observe_mutations(
                 }
             }
 
+            let has_removed_nodes = removed_nodes.size() > 0;
             removed_nodes.clear();
 
-            // tabster.modalizer?.hiddenUpdate();
+            if has_removed_nodes {
+                let deloser = tabster.borrow().deloser.clone();
+                if let Some(deloser) = deloser {
+                    deloser.borrow_mut().on_nodes_removed();
+                }
+            }
+
+            let modalizer = tabster.borrow().modalizer.clone();
+            if let Some(modalizer) = modalizer {
+                modalizer.borrow_mut().hidden_update();
+            }
+
+            ObservedElementAPI::check_waiting(&tabster);
         }
     };
 
     let observer = DOM::create_mutation_observer(on_mutation);
-    let init = MutationObserverInit::new();
-    init.set_child_list(true);
-    init.set_subtree(true);
-    init.set_attributes(true);
-    let val = serde_wasm_bindgen::to_value(&vec![TABSTER_ATTRIBUTE_NAME]).unwrap_throw();
-    init.set_attribute_filter(&val);
-
-    observer.observe_with_options(&doc, &init).unwrap_throw();
+    observer
+        .observe_with_options(doc, &mutation_observer_init())
+        .unwrap_throw();
 
     if sync_state {
         let doc = get_window().document().unwrap_throw();
         update_tabster_elements(
             tabster,
             &get_window,
-            &doc,
+            &observer,
             element_by_uid,
             doc.body().unwrap_throw().into(),
             None,
@@ -128,10 +136,20 @@ pub fn observe_mutations(
     })
 }
 
+fn mutation_observer_init() -> MutationObserverInit {
+    let init = MutationObserverInit::new();
+    init.set_child_list(true);
+    init.set_subtree(true);
+    init.set_attributes(true);
+    let val = serde_wasm_bindgen::to_value(&vec![TABSTER_ATTRIBUTE_NAME]).unwrap_throw();
+    init.set_attribute_filter(&val);
+    init
+}
+
 fn update_tabster_elements(
     tabster: Arc<RefCell<TabsterCore>>,
     get_window: &Arc<GetWindow>,
-    doc: &Document,
+    observer: &MutationObserver,
     element_by_uid: Arc<RefCell<Option<String>>>,
     node: Node,
     removed: Option<bool>,
@@ -144,32 +162,43 @@ fn update_tabster_elements(
         }
     }
 
-    process_node(&tabster, node.clone().dyn_into().unwrap_throw(), removed);
+    process_node(
+        &tabster,
+        observer,
+        node.clone().dyn_into().unwrap_throw(),
+        removed,
+    );
 
-    let walker = create_element_tree_walker(doc, &node, move |element| {
-        return process_node(&tabster, element.dyn_into().unwrap_throw(), removed);
+    let (Ok(element), Some(doc)) = (node.clone().dyn_into::<Element>(), node.owner_document())
+    else {
+        return;
+    };
+
+    let dom = WebDOM::new(doc);
+    let mut walker = create_element_tree_walker(&dom, &element, {
+        let observer = observer.clone();
+        move |element: &Element| process_node(&tabster, &observer, element.clone(), removed)
     });
 
-    if let Some(walker) = walker {
-        while walker.next_node().unwrap_throw().is_some() {
-            /* Iterating for the sake of calling processNode() callback. */
-        }
+    while walker.next_node().is_some() {
+        /* Iterating for the sake of calling processNode() callback. */
     }
 }
 
 fn process_node(
     tabster: &Arc<RefCell<TabsterCore>>,
+    observer: &MutationObserver,
     element: Element,
     removed: Option<bool>,
-) -> u32 {
+) -> FilterResult {
     if element.node_type() == Node::TEXT_NODE {
         // It might actually be a text node.
-        return *NodeFilterEnum::FilterSkip;
+        return FilterResult::Skip;
     }
 
     // TODO new code
     if !element.is_instance_of::<HtmlElement>() {
-        return *NodeFilterEnum::FilterSkip;
+        return FilterResult::Skip;
     }
 
     // const uid = (element as HTMLElementWithUID).__tabsterElementUID;
@@ -182,6 +211,15 @@ fn process_node(
     }
     // }
 
+    // The document observer doesn't see the changes inside the shadow roots.
+    if !removed.unwrap_or_default() {
+        if let Some(shadow_root) = element.shadow_root() {
+            observer
+                .observe_with_options(&shadow_root, &mutation_observer_init())
+                .unwrap_throw();
+        }
+    }
+
     let element: HtmlElement = element.dyn_into().unwrap_throw();
     if get_tabster_on_element(&tabster, &element).is_some()
         || element.has_attribute(TABSTER_ATTRIBUTE_NAME)
@@ -189,5 +227,5 @@ fn process_node(
         update_tabster_by_attribute(&tabster, &element, removed);
     }
 
-    *NodeFilterEnum::FilterSkip
+    FilterResult::Skip
 }
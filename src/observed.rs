@@ -0,0 +1,246 @@
+use web_sys::{
+    js_sys::{Function, Promise},
+    wasm_bindgen::{JsValue, UnwrapThrowExt},
+    HtmlElement,
+};
+
+use crate::{
+    consts::observed_element_accessibilities,
+    dom_api::DOM,
+    keyborg::native_focus,
+    tabster::TabsterCore,
+    types::{GetWindow, ObservedElementAccessibility, DOMAPI},
+    utils::WeakHTMLElement,
+    web::set_timeout,
+};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+
+/// A pending `request_focus()` call.
+pub struct ObservedElementAsyncRequest {
+    /// Resolves to `true` once the element is focused, to `false` when the
+    /// request times out or is cancelled.
+    pub result: Promise,
+    cancel: Rc<dyn Fn()>,
+}
+
+impl ObservedElementAsyncRequest {
+    pub fn cancel(&self) {
+        (self.cancel)();
+    }
+}
+
+struct ObservedElementWaiting {
+    request_id: usize,
+    timer: i32,
+    resolve: Function,
+}
+
+pub struct ObservedElementAPI {
+    tabster: Arc<RefCell<TabsterCore>>,
+    win: Arc<GetWindow>,
+    /// The elements with the `observed` props by name, in the order they appeared.
+    observed_by_name: HashMap<String, Vec<WeakHTMLElement>>,
+    /// One pending focus request per name, a newer request cancels the older one.
+    waiting: HashMap<String, ObservedElementWaiting>,
+    last_request_id: usize,
+}
+
+impl ObservedElementAPI {
+    pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
+        Self {
+            tabster,
+            win: get_window,
+            observed_by_name: HashMap::new(),
+            waiting: HashMap::new(),
+            last_request_id: 0,
+        }
+    }
+
+    /// Resolves the pending requests with `false`.
+    pub fn dispose(&mut self) {
+        let names: Vec<_> = self.waiting.keys().cloned().collect();
+        for name in names {
+            self.resolve_waiting(&name, false);
+        }
+
+        self.observed_by_name.clear();
+    }
+
+    /// Returns the first element with the name which is still in the DOM and
+    /// satisfies the accessibility (`ObservedElementAccessibilities::Any` by default).
+    pub fn get_element(
+        &self,
+        observed_name: &str,
+        accessibility: Option<ObservedElementAccessibility>,
+    ) -> Option<HtmlElement> {
+        let elements = self.observed_by_name.get(observed_name)?;
+        let focusable = self.tabster.borrow().focusable.clone().unwrap_throw();
+        let focusable = focusable.borrow();
+
+        elements
+            .iter()
+            .filter_map(|element| element.get())
+            .find(
+                |element| match accessibility.unwrap_or(observed_element_accessibilities::ANY) {
+                    observed_element_accessibilities::ACCESSIBLE => {
+                        focusable.is_accessible(element)
+                    }
+                    observed_element_accessibilities::FOCUSABLE => {
+                        focusable.is_focusable(element, Some(true), None, None)
+                    }
+                    _ => true,
+                },
+            )
+    }
+
+    /// Focuses the element with the name, waiting up to `timeout` milliseconds
+    /// for it to appear in the DOM and become focusable.
+    pub fn request_focus(
+        &mut self,
+        observed_name: &str,
+        timeout: i32,
+    ) -> ObservedElementAsyncRequest {
+        self.resolve_waiting(observed_name, false);
+
+        self.last_request_id += 1;
+        let request_id = self.last_request_id;
+
+        let mut resolve = None::<Function>;
+        let result = Promise::new(&mut |res, _| resolve = Some(res));
+        let resolve = resolve.unwrap_throw();
+
+        let cancel: Rc<dyn Fn()> = Rc::new({
+            let tabster = self.tabster.clone();
+            let observed_name = observed_name.to_string();
+            move || {
+                let Some(api) = tabster.borrow().observed_element.clone() else {
+                    return;
+                };
+
+                let mut api = api.borrow_mut();
+                if api
+                    .waiting
+                    .get(&observed_name)
+                    .is_some_and(|waiting| waiting.request_id == request_id)
+                {
+                    api.resolve_waiting(&observed_name, false);
+                }
+            }
+        });
+
+        let timer = set_timeout(
+            &(self.win)(),
+            {
+                let cancel = cancel.clone();
+                move || cancel()
+            },
+            timeout,
+        );
+
+        self.waiting.insert(
+            observed_name.to_string(),
+            ObservedElementWaiting {
+                request_id,
+                timer,
+                resolve,
+            },
+        );
+
+        // The element might be there already.
+        if let Some(element) = self.get_element(
+            observed_name,
+            Some(observed_element_accessibilities::FOCUSABLE),
+        ) {
+            let resolve = self.take_waiting(observed_name);
+            focus_and_resolve(element, resolve);
+        }
+
+        ObservedElementAsyncRequest { result, cancel }
+    }
+
+    /// Called when the `observed` props of the element are set, changed or removed.
+    pub(crate) fn on_observed_element_update(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+        names: Option<Vec<String>>,
+    ) {
+        let Some(api) = tabster.borrow().observed_element.clone() else {
+            return;
+        };
+
+        {
+            let mut api = api.borrow_mut();
+            let win = api.win.clone();
+
+            // The elements which are gone are dropped along the way.
+            api.observed_by_name.retain(|_, elements| {
+                elements.retain(|el| el.get().is_some_and(|el| el != *element));
+                !elements.is_empty()
+            });
+
+            for name in names.unwrap_or_default() {
+                api.observed_by_name
+                    .entry(name)
+                    .or_default()
+                    .push(WeakHTMLElement::new(win.clone(), element.clone(), None));
+            }
+        }
+
+        Self::check_waiting(tabster);
+    }
+
+    /// Fulfills the pending requests whose elements have become focusable.
+    /// Called by the mutation observer after every batch of DOM changes.
+    pub(crate) fn check_waiting(tabster: &Arc<RefCell<TabsterCore>>) {
+        let Some(api) = tabster.borrow().observed_element.clone() else {
+            return;
+        };
+
+        let ready: Vec<_> = {
+            let mut api = api.borrow_mut();
+            let names: Vec<_> = api.waiting.keys().cloned().collect();
+
+            names
+                .into_iter()
+                .filter_map(|name| {
+                    let element =
+                        api.get_element(&name, Some(observed_element_accessibilities::FOCUSABLE))?;
+                    Some((element, api.take_waiting(&name)))
+                })
+                .collect()
+        };
+
+        for (element, resolve) in ready {
+            focus_and_resolve(element, resolve);
+        }
+    }
+
+    fn take_waiting(&mut self, observed_name: &str) -> Option<Function> {
+        let waiting = self.waiting.remove(observed_name)?;
+        (self.win)().clear_timeout_with_handle(waiting.timer);
+        Some(waiting.resolve)
+    }
+
+    fn resolve_waiting(&mut self, observed_name: &str, value: bool) {
+        if let Some(resolve) = self.take_waiting(observed_name) {
+            resolve
+                .call1(&JsValue::NULL, &JsValue::from_bool(value))
+                .unwrap_throw();
+        }
+    }
+}
+
+fn focus_and_resolve(element: HtmlElement, resolve: Option<Function>) {
+    native_focus(element.clone());
+
+    let is_focused = element
+        .owner_document()
+        .and_then(|doc| DOM::get_active_element(&doc))
+        .is_some_and(|active_element| active_element == *element);
+
+    if let Some(resolve) = resolve {
+        resolve
+            .call1(&JsValue::NULL, &JsValue::from_bool(is_focused))
+            .unwrap_throw();
+    }
+}
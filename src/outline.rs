@@ -0,0 +1,406 @@
+use web_sys::{
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    Event, HtmlElement,
+};
+
+use crate::{
+    dom_api::WebDOM,
+    tabster::TabsterCore,
+    types::{DOMBackend, GetWindow},
+    web::{add_event_listener_with_bool, EventListenerHandle},
+};
+use std::{cell::RefCell, sync::Arc};
+
+#[derive(Clone)]
+pub struct OutlineProps {
+    /// The class added to the `<body>`, the native outlines are hidden inside it.
+    pub area_class: String,
+    pub outline_class: String,
+    pub outline_color: String,
+    pub outline_width: u32,
+    pub z_index: String,
+}
+
+impl Default for OutlineProps {
+    fn default() -> Self {
+        Self {
+            area_class: "tabster-focus-outline-area".to_string(),
+            outline_class: "tabster-focus-outline".to_string(),
+            outline_color: "#ff4500".to_string(),
+            outline_width: 2,
+            z_index: "2147483647".to_string(),
+        }
+    }
+}
+
+/// left, top, width, height
+type OutlinePosition = (f64, f64, f64, f64);
+
+pub struct OutlineAPI {
+    tabster: Arc<RefCell<TabsterCore>>,
+    win: Arc<GetWindow>,
+    props: OutlineProps,
+    style: Option<HtmlElement>,
+    outline: Option<HtmlElement>,
+    outlined_element: Option<HtmlElement>,
+    cur_pos: Option<OutlinePosition>,
+    is_visible: bool,
+    event_listener_handle_scroll: Arc<RefCell<Option<EventListenerHandle>>>,
+    event_listener_handle_resize: Arc<RefCell<Option<EventListenerHandle>>>,
+}
+
+impl OutlineAPI {
+    pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
+        let event_listener_handle_scroll: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+        let event_listener_handle_resize: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+
+        tabster.borrow_mut().queue_init({
+            let tabster = tabster.clone();
+            let get_window = get_window.clone();
+            let event_listener_handle_scroll = event_listener_handle_scroll.clone();
+            let event_listener_handle_resize = event_listener_handle_resize.clone();
+            move || {
+                let keyborg = tabster.borrow().keyboard_navigation.clone();
+                if let Some(keyborg) = keyborg {
+                    let tabster = tabster.clone();
+                    keyborg.subscribe(move |_| {
+                        Self::on_keyboard_navigation_state_changed(&tabster);
+                    });
+                }
+
+                if let Some(focused_element) = tabster.borrow_mut().focused_element.as_mut() {
+                    let tabster = tabster.clone();
+                    focused_element.subscribe(move |_, element, _| {
+                        Self::with_outline(&tabster, |outline| outline.on_focus(element));
+                    });
+                }
+
+                let win = get_window();
+
+                *event_listener_handle_scroll.borrow_mut() = Some(add_event_listener_with_bool(
+                    win.clone(),
+                    "scroll",
+                    {
+                        let tabster = tabster.clone();
+                        move |event: Event| {
+                            Self::with_outline(&tabster, |outline| outline.on_scroll(event));
+                        }
+                    },
+                    true, // Capture!
+                ));
+
+                *event_listener_handle_resize.borrow_mut() = Some(add_event_listener_with_bool(
+                    win,
+                    "resize",
+                    move |_: Event| {
+                        Self::with_outline(&tabster, |outline| {
+                            outline.cur_pos = None;
+                            outline.set_outline_position();
+                        });
+                    },
+                    false,
+                ));
+            }
+        });
+
+        Self {
+            tabster,
+            win: get_window,
+            props: Default::default(),
+            style: None,
+            outline: None,
+            outlined_element: None,
+            cur_pos: None,
+            is_visible: false,
+            event_listener_handle_scroll,
+            event_listener_handle_resize,
+        }
+    }
+
+    /// Enables the custom outline, hiding the native outlines for the document.
+    pub fn setup(&mut self, props: Option<OutlineProps>) {
+        let Some(doc) = (self.win)().document() else {
+            return;
+        };
+        let Some(body) = doc.body() else {
+            return;
+        };
+
+        let props = props.unwrap_or_default();
+
+        if self.style.is_none() {
+            let style: HtmlElement = doc.create_element("style").unwrap_throw().unchecked_into();
+            style.set_text_content(Some(&format!(
+                ".{area} *, .{area} *:focus {{ outline: none !important; }} \
+                 .{outline} {{ display: none; position: absolute; pointer-events: none; \
+                 box-sizing: border-box; z-index: {z_index}; }}",
+                area = props.area_class,
+                outline = props.outline_class,
+                z_index = props.z_index,
+            )));
+            doc.head()
+                .map(HtmlElement::from)
+                .unwrap_or(body.clone())
+                .append_child(&style)
+                .unwrap_throw();
+            self.style = Some(style);
+        }
+
+        body.class_list()
+            .remove_1(&self.props.area_class)
+            .unwrap_throw();
+        body.class_list().add_1(&props.area_class).unwrap_throw();
+
+        self.props = props;
+        self.cur_pos = None;
+    }
+
+    /// Removes the outline and brings the native outlines back.
+    pub fn dispose(&mut self) {
+        if let Some(handle) = self.event_listener_handle_scroll.borrow_mut().take() {
+            handle.remove();
+        }
+
+        if let Some(handle) = self.event_listener_handle_resize.borrow_mut().take() {
+            handle.remove();
+        }
+
+        if let Some(outline) = self.outline.take() {
+            outline.remove();
+        }
+
+        if let Some(style) = self.style.take() {
+            style.remove();
+        }
+
+        if let Some(body) = (self.win)().document().and_then(|doc| doc.body()) {
+            body.class_list()
+                .remove_1(&self.props.area_class)
+                .unwrap_throw();
+        }
+
+        self.outlined_element = None;
+        self.cur_pos = None;
+        self.is_visible = false;
+    }
+
+    fn with_outline(tabster: &Arc<RefCell<TabsterCore>>, f: impl FnOnce(&mut Self)) {
+        let outline = tabster.borrow().outline.clone();
+        if let Some(outline) = outline {
+            f(&mut outline.borrow_mut());
+        }
+    }
+
+    fn on_keyboard_navigation_state_changed(tabster: &Arc<RefCell<TabsterCore>>) {
+        let focused_element = {
+            let tabster = tabster.borrow();
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        Self::with_outline(tabster, |outline| outline.on_focus(focused_element));
+    }
+
+    fn on_focus(&mut self, element: Option<HtmlElement>) {
+        if !self.update_element(element) && self.is_visible {
+            self.set_visibility(false);
+        }
+    }
+
+    fn update_element(&mut self, element: Option<HtmlElement>) -> bool {
+        self.outlined_element = None;
+        self.cur_pos = None;
+
+        let is_navigating_with_keyboard = self
+            .tabster
+            .borrow()
+            .keyboard_navigation
+            .as_ref()
+            .is_some_and(|keyborg| keyborg.is_navigating_with_keyboard());
+
+        if !is_navigating_with_keyboard {
+            return false;
+        }
+
+        let Some(element) = element else {
+            return false;
+        };
+
+        // It's hard (and not necessary) to come up with every possible
+        // condition when there should be no outline.
+        if element.tag_name() == "IFRAME" {
+            return false;
+        }
+
+        self.outlined_element = Some(element);
+        self.set_outline_position();
+
+        true
+    }
+
+    fn on_scroll(&mut self, event: Event) {
+        let Some(outlined_element) = self.outlined_element.as_ref() else {
+            return;
+        };
+
+        let target = event
+            .target()
+            .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+
+        // The document itself is scrolled.
+        let is_parent = match target.as_ref() {
+            Some(target) => target.contains(Some(outlined_element)),
+            None => true,
+        } || target
+            .is_some_and(|target| target.node_type() == web_sys::Node::DOCUMENT_NODE);
+
+        if !is_parent {
+            return;
+        }
+
+        self.cur_pos = None;
+        self.set_outline_position();
+    }
+
+    fn set_outline_position(&mut self) {
+        let Some(element) = self.outlined_element.clone() else {
+            return;
+        };
+
+        let win = (self.win)();
+        let Some(doc) = win.document() else {
+            return;
+        };
+        let dom = WebDOM::new(doc);
+        let rect = dom.get_bounding_client_rect(&element);
+        let position = (rect.left, rect.top, rect.width(), rect.height());
+
+        if self.cur_pos == Some(position) {
+            return;
+        }
+
+        self.cur_pos = Some(position);
+
+        let (mut left, mut top, width, height) = position;
+        let mut right = left + width;
+        let mut bottom = top + height;
+        let mut has_absolute_positioned_parent = false;
+        let mut has_fixed_positioned_parent = false;
+
+        let mut parent = element.parent_element();
+        while let Some(p) = parent {
+            if p.tag_name() == "BODY" || p.tag_name() == "HTML" {
+                break;
+            }
+
+            let Some(style) = win.get_computed_style(&p).ok().flatten() else {
+                break;
+            };
+            let position = style.get_property_value("position").unwrap_or_default();
+            let overflow = style.get_property_value("overflow").unwrap_or_default();
+
+            if position == "absolute" {
+                has_absolute_positioned_parent = true;
+            } else if position == "fixed" || position == "sticky" {
+                has_fixed_positioned_parent = true;
+            }
+
+            // The element might be partially visible within its scrollable parent,
+            // reduce the bounding rect if this is the case.
+            if overflow != "visible"
+                && ((!has_absolute_positioned_parent && !has_fixed_positioned_parent)
+                    || overflow == "hidden")
+            {
+                let parent_rect = dom.get_bounding_client_rect(&p);
+                left = left.max(parent_rect.left);
+                top = top.max(parent_rect.top);
+                right = right.min(parent_rect.right);
+                bottom = bottom.min(parent_rect.bottom);
+            }
+
+            if has_fixed_positioned_parent {
+                break;
+            }
+
+            parent = p.parent_element();
+        }
+
+        if right <= left || bottom <= top {
+            // Scrolled out of the visible area.
+            self.set_visibility(false);
+            return;
+        }
+
+        let outline = self.get_dom();
+        let style = outline.style();
+        let width = self.props.outline_width as f64;
+
+        if has_fixed_positioned_parent {
+            style.set_property("position", "fixed").unwrap_throw();
+        } else {
+            style.set_property("position", "absolute").unwrap_throw();
+            let scroll_x = win.scroll_x().unwrap_or_default();
+            let scroll_y = win.scroll_y().unwrap_or_default();
+            left += scroll_x;
+            right += scroll_x;
+            top += scroll_y;
+            bottom += scroll_y;
+        }
+
+        style
+            .set_property("left", &format!("{}px", left - width))
+            .unwrap_throw();
+        style
+            .set_property("top", &format!("{}px", top - width))
+            .unwrap_throw();
+        style
+            .set_property("width", &format!("{}px", right - left + width * 2.0))
+            .unwrap_throw();
+        style
+            .set_property("height", &format!("{}px", bottom - top + width * 2.0))
+            .unwrap_throw();
+        style
+            .set_property(
+                "border",
+                &format!("{}px solid {}", width, self.props.outline_color),
+            )
+            .unwrap_throw();
+
+        self.set_visibility(true);
+    }
+
+    fn get_dom(&mut self) -> HtmlElement {
+        if let Some(outline) = self.outline.as_ref() {
+            if outline.is_connected() {
+                return outline.clone();
+            }
+        }
+
+        let doc = (self.win)().document().unwrap_throw();
+        let outline: HtmlElement = doc.create_element("div").unwrap_throw().unchecked_into();
+        outline.set_class_name(&self.props.outline_class);
+        outline.set_attribute("aria-hidden", "true").unwrap_throw();
+        doc.body()
+            .unwrap_throw()
+            .append_child(&outline)
+            .unwrap_throw();
+        self.outline = Some(outline.clone());
+
+        outline
+    }
+
+    fn set_visibility(&mut self, visible: bool) {
+        self.is_visible = visible;
+
+        if let Some(outline) = self.outline.as_ref() {
+            outline
+                .style()
+                .set_property("display", if visible { "block" } else { "none" })
+                .unwrap_throw();
+        }
+    }
+}
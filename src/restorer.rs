@@ -0,0 +1,286 @@
+use web_sys::{
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    Event, EventInit, FocusEvent, HtmlElement,
+};
+
+use crate::{
+    consts::{restorer_types, RESTORER_RESTORE_FOCUS_EVENT_NAME},
+    dom_api::DOM,
+    instance::get_tabster_on_element,
+    keyborg::native_focus,
+    tabster::TabsterCore,
+    types::{self, GetWindow, DOMAPI},
+    utils::{TabsterPart, WeakHTMLElement},
+    web::{add_event_listener, add_event_listener_with_bool, EventListenerHandle},
+};
+use std::{
+    cell::{Cell, RefCell},
+    ops::Deref,
+    rc::Rc,
+    sync::Arc,
+};
+
+const HISTORY_DEPTH: usize = 10;
+
+pub type ArcCellRestorer = Arc<RefCell<Restorer>>;
+
+pub struct Restorer {
+    part: TabsterPart<types::RestorerProps>,
+    has_focus: Rc<Cell<bool>>,
+    event_listener_handle_focusout: Option<EventListenerHandle>,
+    event_listener_handle_focusin: Option<EventListenerHandle>,
+}
+
+impl Deref for Restorer {
+    type Target = TabsterPart<types::RestorerProps>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.part
+    }
+}
+
+impl Restorer {
+    pub fn new(
+        tabster: Arc<RefCell<TabsterCore>>,
+        element: &HtmlElement,
+        props: types::RestorerProps,
+    ) -> Arc<RefCell<Self>> {
+        let is_source = props.r#type == restorer_types::SOURCE;
+        let mut this = Self {
+            part: TabsterPart::new(tabster, element.clone(), props),
+            has_focus: Default::default(),
+            event_listener_handle_focusout: None,
+            event_listener_handle_focusin: None,
+        };
+
+        if is_source {
+            let active_element = element
+                .owner_document()
+                .and_then(|doc| DOM::get_active_element(&doc));
+            this.has_focus.set(DOM::node_contains(
+                Some(element.clone().into()),
+                active_element.map(Into::into),
+            ));
+
+            this.event_listener_handle_focusout =
+                Some(add_event_listener(element.clone(), "focusout", {
+                    let element = element.clone();
+                    let has_focus = this.has_focus.clone();
+                    move |event: FocusEvent| {
+                        let related_target = event
+                            .related_target()
+                            .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+
+                        // The focus is going nowhere, the element is being hidden or removed.
+                        if related_target.is_none() {
+                            dispatch_restore_focus_event(&element);
+                        }
+
+                        if !DOM::node_contains(Some(element.clone().into()), related_target) {
+                            has_focus.set(false);
+                        }
+                    }
+                }));
+            this.event_listener_handle_focusin =
+                Some(add_event_listener(element.clone(), "focusin", {
+                    let has_focus = this.has_focus.clone();
+                    move |_: FocusEvent| {
+                        has_focus.set(true);
+                    }
+                }));
+        }
+
+        Arc::new(RefCell::new(this))
+    }
+
+    pub fn set_props(&mut self, props: types::RestorerProps) {
+        self.part.set_props(props);
+    }
+
+    pub fn dispose(&mut self) {
+        if let Some(handle) = self.event_listener_handle_focusout.take() {
+            handle.remove();
+        }
+
+        if let Some(handle) = self.event_listener_handle_focusin.take() {
+            handle.remove();
+        }
+
+        if self.get_props().r#type == restorer_types::SOURCE && self.has_focus.get() {
+            let doc = (self.tabster.borrow().get_window)()
+                .document()
+                .unwrap_throw();
+
+            if let Some(body) = doc.body() {
+                dispatch_restore_focus_event(&body);
+            }
+        }
+    }
+}
+
+fn dispatch_restore_focus_event(target: &HtmlElement) {
+    let init = EventInit::new();
+    init.set_bubbles(true);
+    let event =
+        Event::new_with_event_init_dict(RESTORER_RESTORE_FOCUS_EVENT_NAME, &init).unwrap_throw();
+    target.dispatch_event(&event).unwrap_throw();
+}
+
+pub struct RestorerAPI {
+    tabster: Arc<RefCell<TabsterCore>>,
+    win: Arc<GetWindow>,
+    /// Recently focused restorer targets, most recent last.
+    history: Vec<WeakHTMLElement>,
+    event_listener_handle_restore_focus: Arc<RefCell<Option<EventListenerHandle>>>,
+}
+
+impl RestorerAPI {
+    pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<GetWindow>) -> Self {
+        let event_listener_handle_restore_focus: Arc<RefCell<Option<EventListenerHandle>>> =
+            Default::default();
+
+        tabster.borrow_mut().queue_init({
+            let tabster = tabster.clone();
+            let get_window = get_window.clone();
+            let event_listener_handle_restore_focus = event_listener_handle_restore_focus.clone();
+            move || {
+                if let Some(focused_element) = tabster.borrow_mut().focused_element.as_mut() {
+                    let tabster = tabster.clone();
+                    focused_element.subscribe(move |_, element, _| {
+                        if let Some(element) = element {
+                            Self::on_focus_in(&tabster, element);
+                        }
+                    });
+                }
+
+                *event_listener_handle_restore_focus.borrow_mut() =
+                    Some(add_event_listener_with_bool(
+                        get_window(),
+                        RESTORER_RESTORE_FOCUS_EVENT_NAME,
+                        move |event: Event| {
+                            Self::on_restore_focus(&tabster, event);
+                        },
+                        false,
+                    ));
+            }
+        });
+
+        Self {
+            tabster,
+            win: get_window,
+            history: vec![],
+            event_listener_handle_restore_focus,
+        }
+    }
+
+    pub fn dispose(&mut self) {
+        if let Some(handle) = self.event_listener_handle_restore_focus.borrow_mut().take() {
+            handle.remove();
+        }
+
+        self.history.clear();
+    }
+
+    pub fn create_restorer(
+        &mut self,
+        element: &HtmlElement,
+        props: types::RestorerProps,
+    ) -> ArcCellRestorer {
+        let is_target = props.r#type == restorer_types::TARGET;
+        let restorer = Restorer::new(self.tabster.clone(), element, props);
+
+        let active_element = element
+            .owner_document()
+            .and_then(|doc| DOM::get_active_element(&doc));
+        if is_target && active_element.as_deref() == Some(element) {
+            self.add_to_history(element.clone());
+        }
+
+        restorer
+    }
+
+    fn on_restore_focus(tabster: &Arc<RefCell<TabsterCore>>, event: Event) {
+        let Some(api) = tabster.borrow().restorer.clone() else {
+            return;
+        };
+
+        let doc = (api.borrow().win)().document().unwrap_throw();
+        let Some(body) = doc.body() else {
+            return;
+        };
+
+        // Don't restore focus if focus isn't lost to body.
+        if DOM::get_active_element(&doc).as_deref() != Some(&body) {
+            return;
+        }
+
+        let is_navigating_with_keyboard = tabster
+            .borrow()
+            .keyboard_navigation
+            .as_ref()
+            .is_some_and(|keyborg| keyborg.is_navigating_with_keyboard());
+
+        // A mouse user clicking elsewhere in the document doesn't need the focus back.
+        if !is_navigating_with_keyboard
+            && DOM::node_contains(
+                Some(body.clone().into()),
+                event.target().and_then(|target| target.dyn_into().ok()),
+            )
+        {
+            return;
+        }
+
+        let last_focused = {
+            let mut api = api.borrow_mut();
+            let mut last_focused = None;
+
+            // Clean up the history from the elements which are not in the DOM anymore.
+            while let Some(el) = api.history.pop() {
+                if let Some(el) = el.get() {
+                    last_focused = Some(el);
+                    break;
+                }
+            }
+
+            last_focused
+        };
+
+        if let Some(last_focused) = last_focused {
+            native_focus(last_focused);
+        }
+    }
+
+    fn on_focus_in(tabster: &Arc<RefCell<TabsterCore>>, element: HtmlElement) {
+        let is_target = get_tabster_on_element(tabster, &element)
+            .and_then(|tabster_on_element| tabster_on_element.borrow().restorer.clone())
+            .is_some_and(|restorer| restorer.borrow().get_props().r#type == restorer_types::TARGET);
+
+        if !is_target {
+            return;
+        }
+
+        let Some(api) = tabster.borrow().restorer.clone() else {
+            return;
+        };
+
+        api.borrow_mut().add_to_history(element);
+    }
+
+    fn add_to_history(&mut self, element: HtmlElement) {
+        // Don't add the same element to history consecutively.
+        if self
+            .history
+            .last()
+            .and_then(|el| el.get())
+            .is_some_and(|el| el == element)
+        {
+            return;
+        }
+
+        self.history
+            .push(WeakHTMLElement::new(self.win.clone(), element, None));
+
+        let overflow = self.history.len().saturating_sub(HISTORY_DEPTH);
+        self.history.drain(..overflow);
+    }
+}
@@ -115,6 +115,12 @@ impl RootAPI {
         }
     }
 
+    pub fn dispose(&mut self) {
+        self.auto_root_unwait();
+        self.roots.clear();
+        self.root_by_id.clear();
+    }
+
     fn auto_root_create(&mut self) -> Option<ArcCellRoot> {
         let doc = (self.win)().document().unwrap_throw();
         let body = doc.body();
@@ -179,10 +185,7 @@ impl RootAPI {
         // Normally, the initialization starts on the next tick after the tabster
         // instance creation. However, if the application starts using it before
         // the next tick, we need to make sure the initialization is done.
-        {
-            let mut tabster = tabster.try_borrow_mut().unwrap_throw();
-            tabster.drain_init_queue();
-        }
+        TabsterCore::drain_init_queue(tabster);
 
         let mut root: Option<ArcCellRoot> = None;
         let mut modalizer = None::<ArcCellModalizer>;
@@ -275,7 +278,7 @@ impl RootAPI {
             if groupper.is_none() && (modalizer.is_none() || cur_modalizer.is_some()) {
                 if let Some(cur_groupper) = cur_groupper {
                     if modalizer.is_some() {
-                        let mut cur_groupper_ref = cur_groupper.borrow_mut();
+                        let cur_groupper_ref = cur_groupper.borrow();
 
                         let user_id = {
                             if let Some(modalizer) = modalizer.as_ref() {
@@ -287,7 +290,7 @@ impl RootAPI {
                         let active_id = {
                             let tabster = tabster.borrow();
                             if let Some(modalizer) = tabster.modalizer.as_ref() {
-                                modalizer.active_id.clone()
+                                modalizer.borrow().active_id.clone()
                             } else {
                                 None
                             }
@@ -350,8 +353,8 @@ impl RootAPI {
 
         // No root element could be found, try to get an auto root
         if root.is_none() {
-            let tabster = tabster.borrow_mut();
-            if let Some(root_api) = &tabster.root {
+            let root_api = tabster.borrow().root.clone();
+            if let Some(root_api) = &root_api {
                 let mut root_api = root_api.borrow_mut();
                 if root_api.auto_root.is_some() {
                     if let Some(owner_document) = element.owner_document() {
@@ -1,15 +1,22 @@
-use web_sys::HtmlElement;
+use web_sys::{
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    FocusEvent, HtmlElement, KeyboardEvent,
+};
 
 use crate::{
-    dom_api::DOM,
+    consts::keys,
+    dom_api::{WebDOM, DOM},
     groupper::ArcCellGroupper,
+    keyborg::{is_focusing_programmatically, native_focus},
     modalizer::ArcCellModalizer,
     mover::ArcCellMover,
     root::RootAPI,
     tabster::TabsterCore,
-    types::{self, GetTabsterContextOptions, NextTabbable, DOMAPI},
-    utils::get_last_child,
-    web::set_timeout,
+    types::{
+        self, FocusSource, FocusedElementDetail, GetTabsterContextOptions, NextTabbable, DOMAPI,
+    },
+    utils::{get_last_child, get_uncontrolled_completely_container, should_ignore_focus},
+    web::{add_event_listener_with_bool, set_timeout, EventListenerHandle},
 };
 use std::{
     cell::RefCell,
@@ -28,11 +35,13 @@ pub(crate) static FOCUSED_ELEMENT_STATE_IS_TABBING_TIMER: AtomicI32 = AtomicI32:
 pub struct FocusedElementState {
     tabster: Arc<RefCell<TabsterCore>>,
     win: Arc<types::GetWindow>,
-    subscribable: Subscribable<HtmlElement>,
+    subscribable: Subscribable<HtmlElement, FocusedElementDetail>,
+    last_val: Option<HtmlElement>,
+    event_listener_handles: Arc<RefCell<Vec<EventListenerHandle>>>,
 }
 
 impl Deref for FocusedElementState {
-    type Target = Subscribable<HtmlElement>;
+    type Target = Subscribable<HtmlElement, FocusedElementDetail>;
 
     fn deref(&self) -> &Self::Target {
         &self.subscribable
@@ -47,17 +56,280 @@ impl DerefMut for FocusedElementState {
 
 impl FocusedElementState {
     pub fn new(tabster: Arc<RefCell<TabsterCore>>, get_window: Arc<types::GetWindow>) -> Self {
+        let event_listener_handles: Arc<RefCell<Vec<EventListenerHandle>>> = Default::default();
+
+        tabster.borrow_mut().queue_init({
+            let tabster = tabster.clone();
+            let get_window = get_window.clone();
+            let event_listener_handles = event_listener_handles.clone();
+            move || {
+                let win = get_window();
+                let doc = win.document().unwrap_throw();
+                let mut handles = event_listener_handles.borrow_mut();
+
+                // Add these event listeners as capture - we want Tabster to run before user event handlers.
+                handles.push(add_event_listener_with_bool(
+                    doc.clone(),
+                    "focusin",
+                    {
+                        let tabster = tabster.clone();
+                        move |event: FocusEvent| {
+                            let target =
+                                event.composed_path().get(0).dyn_into::<HtmlElement>().ok();
+
+                            if let Some(target) = target {
+                                Self::set_focused_element(
+                                    &tabster,
+                                    Some(target),
+                                    event
+                                        .related_target()
+                                        .and_then(|target| target.dyn_into().ok()),
+                                    Self::get_focus_source(&tabster),
+                                );
+                            }
+                        }
+                    },
+                    true,
+                ));
+                handles.push(add_event_listener_with_bool(
+                    doc.clone(),
+                    "focusout",
+                    {
+                        let tabster = tabster.clone();
+                        move |event: FocusEvent| {
+                            // When the focus moves to another element, focusin follows
+                            // and reports both elements at once.
+                            if event.related_target().is_none() {
+                                Self::set_focused_element(
+                                    &tabster,
+                                    None,
+                                    None,
+                                    Self::get_focus_source(&tabster),
+                                );
+                            }
+                        }
+                    },
+                    true,
+                ));
+                handles.push(add_event_listener_with_bool(
+                    win.clone(),
+                    "keydown",
+                    {
+                        let tabster = tabster.clone();
+                        move |event: KeyboardEvent| Self::on_key_down(&tabster, event)
+                    },
+                    true,
+                ));
+                drop(handles);
+
+                if let Some(active_element) = DOM::get_active_element(&doc) {
+                    if Some(&active_element) != doc.body().as_deref() {
+                        Self::set_focused_element(
+                            &tabster,
+                            active_element.dyn_into().ok(),
+                            None,
+                            FocusSource::Programmatic,
+                        );
+                    }
+                }
+            }
+        });
+
         Self {
             tabster,
             win: get_window,
             subscribable: Subscribable::new(),
+            last_val: None,
+            event_listener_handles,
+        }
+    }
+
+    pub fn dispose(&mut self) {
+        for handle in self.event_listener_handles.borrow_mut().drain(..) {
+            handle.remove();
         }
+
+        let is_tabbing_timer = FOCUSED_ELEMENT_STATE_IS_TABBING_TIMER.swap(0, Ordering::SeqCst);
+        if is_tabbing_timer != 0 {
+            (self.win)().clear_timeout_with_handle(is_tabbing_timer);
+        }
+        FOCUSED_ELEMENT_STATE_IS_TABBING.store(false, Ordering::SeqCst);
+
+        self.subscribable.dispose();
+        self.last_val = None;
     }
 
     pub fn get_focused_element(&self) -> Option<HtmlElement> {
         self.subscribable.get_val()
     }
 
+    /// The last element which had focus, as long as it is still in the document.
+    pub fn get_last_focused_element(&mut self) -> Option<HtmlElement> {
+        if !self.last_val.as_ref().is_some_and(|el| el.is_connected()) {
+            self.last_val = None;
+        }
+
+        self.last_val.clone()
+    }
+
+    fn set_focused_element(
+        tabster: &Arc<RefCell<TabsterCore>>,
+        element: Option<HtmlElement>,
+        related_target: Option<HtmlElement>,
+        source: FocusSource,
+    ) {
+        if tabster.borrow().noop {
+            return;
+        }
+
+        let mut detail = FocusedElementDetail {
+            related_target,
+            source,
+            modalizer_id: None,
+        };
+
+        if let Some(element) = element.as_ref() {
+            if should_ignore_focus(element) {
+                return;
+            }
+
+            detail.modalizer_id =
+                RootAPI::get_tabster_context(tabster, element, Default::default())
+                    .and_then(|ctx| ctx.modalizer)
+                    .map(|modalizer| modalizer.borrow().user_id.clone());
+        }
+
+        let notification = {
+            let mut tabster = tabster.borrow_mut();
+            let Some(focused_element) = tabster.focused_element.as_mut() else {
+                return;
+            };
+
+            if element.is_some() {
+                focused_element.last_val = element.clone();
+            }
+
+            focused_element.subscribable.set_val(element)
+        };
+
+        if let Some(notification) = notification {
+            notification.notify(&detail);
+        }
+    }
+
+    /// Tabster moves the focus on Tab itself, the rest of the programmatic
+    /// focus goes through `native_focus()`. Otherwise the focus follows the
+    /// user input Keyborg has seen last.
+    fn get_focus_source(tabster: &Arc<RefCell<TabsterCore>>) -> FocusSource {
+        if FOCUSED_ELEMENT_STATE_IS_TABBING.load(Ordering::SeqCst) {
+            return FocusSource::Keyboard;
+        }
+
+        if is_focusing_programmatically() {
+            return FocusSource::Programmatic;
+        }
+
+        let is_navigating_with_keyboard = tabster
+            .borrow()
+            .keyboard_navigation
+            .as_ref()
+            .is_some_and(|keyborg| keyborg.is_navigating_with_keyboard());
+
+        if is_navigating_with_keyboard {
+            FocusSource::Keyboard
+        } else {
+            FocusSource::Mouse
+        }
+    }
+
+    fn on_key_down(tabster: &Arc<RefCell<TabsterCore>>, event: KeyboardEvent) {
+        if event.key() != keys::TAB || event.ctrl_key() {
+            return;
+        }
+
+        let current_element = {
+            let tabster = tabster.borrow();
+            if !tabster.control_tab {
+                return;
+            }
+
+            tabster
+                .focused_element
+                .as_ref()
+                .and_then(|focused_element| focused_element.get_focused_element())
+        };
+
+        let Some(current_element) = current_element else {
+            return;
+        };
+
+        if current_element.owner_document().is_none()
+            || current_element.content_editable() == "true"
+        {
+            return;
+        }
+
+        let Some(ctx) = RootAPI::get_tabster_context(tabster, &current_element, Default::default())
+        else {
+            return;
+        };
+
+        if (ctx.ignore_keydown)(event.clone()) {
+            return;
+        }
+
+        let ctx_uncontrolled = ctx.uncontrolled.clone();
+        let next = Self::find_next_tabbable(
+            tabster,
+            ctx,
+            None,
+            Some(current_element.clone()),
+            None,
+            Some(event.shift_key()),
+            Some(true),
+        );
+
+        let Some(NextTabbable {
+            element: Some(next_element),
+            uncontrolled: next_uncontrolled,
+            out_of_dom_order,
+        }) = next
+        else {
+            // Nothing to move to, the browser moves the focus out of the root.
+            return;
+        };
+
+        let out_of_dom_order = out_of_dom_order.unwrap_or_default();
+
+        if ctx_uncontrolled.is_some()
+            || DOM::node_contains(
+                next_uncontrolled.clone().map(Into::into),
+                Some(current_element.clone().into()),
+            )
+        {
+            let uncontrolled_completely_container =
+                get_uncontrolled_completely_container(tabster, &current_element);
+
+            // The area handles Tab itself, unless the next element is something
+            // the browser would not reach, like the one behind an inactive modalizer.
+            if (!out_of_dom_order && next_uncontrolled == ctx_uncontrolled)
+                || uncontrolled_completely_container
+                    .is_some_and(|container| !container.contains(Some(&next_element)))
+            {
+                return;
+            }
+        } else if next_uncontrolled.is_some() || !out_of_dom_order {
+            // The uncontrolled area is entered at its edge and the elements
+            // in DOM order are reached by the default action anyway.
+            return;
+        }
+
+        event.prevent_default();
+        event.stop_immediate_propagation();
+
+        native_focus(next_element);
+    }
+
     pub fn find_next_tabbable(
         tabster: &Arc<RefCell<TabsterCore>>,
         ctx: types::TabsterContext,
@@ -111,19 +383,19 @@ impl FocusedElementState {
                 ignore_accessibility: Option<bool>,
             ) -> Option<types::NextTabbable> {
                 match self {
-                    What::Groupper(groupper) => groupper.borrow_mut().find_next_tabbable(
+                    What::Groupper(groupper) => groupper.borrow().find_next_tabbable(
                         current_element,
                         reference_element,
                         is_backward,
                         ignore_accessibility,
                     ),
-                    What::Mover(mover) => mover.borrow_mut().find_next_tabbable(
+                    What::Mover(mover) => mover.borrow().find_next_tabbable(
                         current_element,
                         reference_element,
                         is_backward,
                         ignore_accessibility,
                     ),
-                    What::Modalizer(modalizer) => modalizer.borrow_mut().find_next_tabbable(
+                    What::Modalizer(modalizer) => modalizer.borrow().find_next_tabbable(
                         current_element,
                         reference_element,
                         is_backward,
@@ -194,9 +466,14 @@ impl FocusedElementState {
                     current_scope_element
                 } else {
                     if let Some(current_scope_element) = current_scope_element {
-                        Some(
-                            get_last_child(&current_scope_element).unwrap_or(current_scope_element),
-                        )
+                        let last_child = current_scope_element
+                            .owner_document()
+                            .and_then(|doc| {
+                                get_last_child(&WebDOM::new(doc), &current_scope_element)
+                            })
+                            .and_then(|last_child| last_child.dyn_into().ok());
+
+                        Some(last_child.unwrap_or(current_scope_element))
                     } else {
                         current_scope_element
                     }
@@ -245,8 +522,8 @@ impl FocusedElementState {
             };
 
             let mut find_props_out = types::FindFocusableOutputProps::default();
-            let tabster = tabster.borrow();
-            let mut focusable = tabster.focusable.as_ref().unwrap().borrow_mut();
+            let focusable = tabster.borrow().focusable.clone().unwrap_throw();
+            let focusable = focusable.borrow();
             let next_element = if is_backward.unwrap_or_default() {
                 focusable.find_prev(find_props, &mut find_props_out)
             } else {
@@ -1,23 +1,91 @@
-pub struct Subscribable<A> {
+use std::rc::Rc;
+
+/// Receives the previous value, the new value and the change details.
+pub type SubscribableCallback<A, B> = Rc<dyn Fn(Option<A>, Option<A>, &B)>;
+
+pub struct Subscribable<A, B> {
     val: Option<A>,
-    callbacks: Vec<Box<dyn FnOnce(A)>>,
+    last_subscription_id: usize,
+    callbacks: Vec<(usize, SubscribableCallback<A, B>)>,
 }
 
-impl<A> Subscribable<A> {
+impl<A, B> Subscribable<A, B> {
     pub fn new() -> Self {
         Self {
             val: None,
+            last_subscription_id: 0,
             callbacks: vec![],
         }
     }
 
-    pub fn subscribe(&mut self, callback: impl FnOnce(A) + 'static) {
-        self.callbacks.push(Box::new(callback));
+    /// Returns the subscription id to pass to `unsubscribe()`.
+    pub fn subscribe(&mut self, callback: impl Fn(Option<A>, Option<A>, &B) + 'static) -> usize {
+        self.last_subscription_id += 1;
+        self.callbacks
+            .push((self.last_subscription_id, Rc::new(callback)));
+        self.last_subscription_id
+    }
+
+    /// Same as `subscribe()`, but the callback is called before the existing ones.
+    pub fn subscribe_first(
+        &mut self,
+        callback: impl Fn(Option<A>, Option<A>, &B) + 'static,
+    ) -> usize {
+        self.last_subscription_id += 1;
+        self.callbacks
+            .insert(0, (self.last_subscription_id, Rc::new(callback)));
+        self.last_subscription_id
+    }
+
+    pub fn unsubscribe(&mut self, id: usize) {
+        self.callbacks
+            .retain(|(subscription_id, _)| *subscription_id != id);
+    }
+
+    pub fn dispose(&mut self) {
+        self.callbacks.clear();
+        self.val = None;
     }
 }
 
-impl<A: Clone> Subscribable<A> {
+impl<A: Clone + PartialEq, B> Subscribable<A, B> {
     pub fn get_val(&self) -> Option<A> {
         self.val.clone()
     }
+
+    /// Updates the value. The subscribers are not called right away: the owner
+    /// is usually borrowed at this point, so the returned notification should be
+    /// sent once the borrow is released.
+    #[must_use]
+    pub fn set_val(&mut self, val: Option<A>) -> Option<SubscribableNotification<A, B>> {
+        if self.val == val {
+            return None;
+        }
+
+        let prev = std::mem::replace(&mut self.val, val.clone());
+
+        Some(SubscribableNotification {
+            prev,
+            val,
+            callbacks: self
+                .callbacks
+                .iter()
+                .map(|(_, callback)| callback.clone())
+                .collect(),
+        })
+    }
+}
+
+pub struct SubscribableNotification<A, B> {
+    prev: Option<A>,
+    val: Option<A>,
+    callbacks: Vec<SubscribableCallback<A, B>>,
+}
+
+impl<A: Clone, B> SubscribableNotification<A, B> {
+    pub fn notify(self, detail: &B) {
+        for callback in self.callbacks {
+            callback(self.prev.clone(), self.val.clone(), detail);
+        }
+    }
 }
@@ -1,17 +1,27 @@
 use crate::{
     console_log,
+    deloser::DeloserAPI,
     dom_api::DOM,
     focusable::FocusableAPI,
     groupper::GroupperAPI,
+    keyborg::Keyborg,
     modalizer::ModalizerAPI,
     mover::MoverAPI,
+    observed::ObservedElementAPI,
+    outline::OutlineAPI,
+    restorer::RestorerAPI,
     root::{RootAPI, WindowWithTabsterInstance},
     state::focused_element::FocusedElementState,
     types::{self, GetWindow, TabsterCoreProps, DOMAPI},
+    utils::dispose_instance_context,
     web::set_timeout,
 };
-use std::{cell::RefCell, collections::HashMap, sync::Arc};
-use web_sys::{js_sys::WeakMap, wasm_bindgen::UnwrapThrowExt, Node, Window};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+use web_sys::{
+    js_sys::{FinalizationRegistry, WeakMap},
+    wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt},
+    Node, Window,
+};
 
 thread_local! {
     static TABSTER_INSTANCE: RefCell<Option<Tabster>> = Default::default();
@@ -50,42 +60,124 @@ impl Tabster {
             core: tabster,
         }
     }
+
+    /// Tears the instance down: the listeners, observers, timers and dummy
+    /// inputs are removed, and the next `create_tabster()` call creates a
+    /// new instance.
+    pub fn dispose(&self) {
+        TabsterCore::dispose(&self.core);
+
+        TABSTER_INSTANCE.with(|instance| {
+            let mut instance = instance.borrow_mut();
+            if instance
+                .as_ref()
+                .is_some_and(|instance| Arc::ptr_eq(&instance.core, &self.core))
+            {
+                *instance = None;
+            }
+        });
+    }
+
+    pub fn keyboard_navigation(&self) -> Keyborg {
+        self.core
+            .borrow()
+            .keyboard_navigation
+            .clone()
+            .unwrap_throw()
+    }
 }
 
-// TODO Memory leak
+type TabsterCoreStorageData = HashMap<String, Arc<RefCell<types::TabsterElementStorage>>>;
+
+/// The element data lives on the Rust side, keyed by a uuid which is kept in a
+/// `WeakMap` against the element. Once the element is garbage-collected, the
+/// `FinalizationRegistry` callback drops its data and disposes its parts.
 struct TabsterCoreStorage {
-    storage: web_sys::js_sys::WeakMap,
-    data: HashMap<String, Arc<RefCell<types::TabsterElementStorage>>>,
+    storage: WeakMap,
+    data: Rc<RefCell<TabsterCoreStorageData>>,
+    registry: FinalizationRegistry,
 }
 
 impl TabsterCoreStorage {
     fn new() -> Self {
+        let data: Rc<RefCell<TabsterCoreStorageData>> = Default::default();
+        // The registry might outlive the storage, the callback shouldn't keep the data alive.
+        let on_collected = Closure::<dyn Fn(JsValue)>::new({
+            let data = Rc::downgrade(&data);
+            move |uuid: JsValue| {
+                let (Some(data), Some(uuid)) = (data.upgrade(), uuid.as_string()) else {
+                    return;
+                };
+
+                let entry = data
+                    .try_borrow_mut()
+                    .ok()
+                    .and_then(|mut data| data.remove(&uuid));
+
+                if let Some(entry) = entry {
+                    dispose_parts(&entry);
+                }
+            }
+        })
+        .into_js_value();
+
         Self {
             storage: WeakMap::new(),
-            data: HashMap::new(),
+            data,
+            registry: FinalizationRegistry::new(on_collected.unchecked_ref()),
         }
     }
+
     fn get_storage_value(&self, el: &Node) -> Option<String> {
         let value = self.storage.get(el);
         value.as_string()
     }
+
     fn get(&self, el: &Node) -> Option<Arc<RefCell<types::TabsterElementStorage>>> {
         let value = self.get_storage_value(el)?;
-        self.data.get(&value).cloned()
+        self.data.borrow().get(&value).cloned()
     }
 
     fn set(&mut self, el: &Node, value: Arc<RefCell<types::TabsterElementStorage>>) {
         let uuid = uuid::Uuid::new_v4().to_string();
-        self.storage
-            .set(el, &web_sys::wasm_bindgen::JsValue::from_str(&uuid));
-        self.data.insert(uuid, value);
+        let held_value = JsValue::from_str(&uuid);
+        self.storage.set(el, &held_value);
+        self.registry.register_with_token(el, &held_value, el);
+        self.data.borrow_mut().insert(uuid, value);
+    }
+
+    fn values(&self) -> Vec<Arc<RefCell<types::TabsterElementStorage>>> {
+        self.data.borrow().values().cloned().collect()
+    }
+
+    fn len(&self) -> usize {
+        self.data.borrow().len()
     }
 
     fn delete(&mut self, el: &Node) {
         if let Some(value) = self.get_storage_value(el) {
-            self.data.remove(&value);
+            self.data.borrow_mut().remove(&value);
         }
-        self.storage.delete(&el);
+        self.registry.unregister(el);
+        self.storage.delete(el);
+    }
+}
+
+/// Disposes the parts of the element which are not owned by an API.
+fn dispose_parts(entry: &Arc<RefCell<types::TabsterElementStorage>>) {
+    let Some(tabster_on_element) = entry.borrow_mut().tabster.take() else {
+        return;
+    };
+    let tabster_on_element = tabster_on_element.borrow();
+
+    if let Some(mover) = tabster_on_element.mover.as_ref() {
+        mover.borrow_mut().dispose();
+    }
+    if let Some(groupper) = tabster_on_element.groupper.as_ref() {
+        groupper.borrow_mut().dispose();
+    }
+    if let Some(restorer) = tabster_on_element.restorer.as_ref() {
+        restorer.borrow_mut().dispose();
     }
 }
 
@@ -103,6 +195,8 @@ pub struct TabsterCore {
     // CoreAPIs
     internal: Option<Arc<RefCell<types::InternalAPI>>>,
     /// .unwrap
+    pub keyboard_navigation: Option<Keyborg>,
+    /// .unwrap
     pub focused_element: Option<FocusedElementState>,
     /// .unwrap
     pub focusable: Option<Arc<RefCell<FocusableAPI>>>,
@@ -112,7 +206,11 @@ pub struct TabsterCore {
     // Extended APIs
     pub groupper: Option<Arc<RefCell<GroupperAPI>>>,
     pub mover: Option<Arc<RefCell<MoverAPI>>>,
-    pub modalizer: Option<ModalizerAPI>,
+    pub modalizer: Option<Arc<RefCell<ModalizerAPI>>>,
+    pub deloser: Option<Arc<RefCell<DeloserAPI>>>,
+    pub restorer: Option<Arc<RefCell<RestorerAPI>>>,
+    pub outline: Option<Arc<RefCell<OutlineAPI>>>,
+    pub observed_element: Option<Arc<RefCell<ObservedElementAPI>>>,
     pub get_parent: Box<dyn Fn(Node) -> Option<Node>>,
 }
 
@@ -120,7 +218,7 @@ impl TabsterCore {
     fn new(win: Window, props: TabsterCoreProps) -> Arc<RefCell<Self>> {
         let get_parent = props
             .get_parent
-            .unwrap_or_else(|| Box::new(move |node| DOM::get_parent_node(Some(node))));
+            .unwrap_or_else(|| Box::new(move |node| DOM::get_composed_parent_node(Some(node))));
         let get_window = {
             let win = win.clone();
             Arc::new(Box::new(move || win.clone()) as GetWindow)
@@ -133,12 +231,17 @@ impl TabsterCore {
             control_tab: props.control_tab.unwrap_or(true),
             get_window: get_window.clone(),
             internal: None,
+            keyboard_navigation: Some(Keyborg::new(win.clone())),
             focused_element: None,
             focusable: None,
             root: None,
             groupper: None,
             mover: None,
             modalizer: None,
+            deloser: None,
+            restorer: None,
+            outline: None,
+            observed_element: None,
             init_queue: Default::default(),
             init_timer: Default::default(),
         }));
@@ -161,6 +264,77 @@ impl TabsterCore {
         tabster
     }
 
+    fn dispose(tabster: &Arc<RefCell<Self>>) {
+        let mut this = tabster.borrow_mut();
+
+        if let Some(timer) = this.init_timer.borrow_mut().take() {
+            (this.get_window)().clear_timeout_with_handle(timer);
+        }
+        this.init_queue.borrow_mut().clear();
+        // Nothing gets queued or observed anymore.
+        this.win = None;
+        this.noop = true;
+
+        let internal = this.internal.take();
+        let groupper = this.groupper.take();
+        let mover = this.mover.take();
+        let modalizer = this.modalizer.take();
+        let deloser = this.deloser.take();
+        let restorer = this.restorer.take();
+        let outline = this.outline.take();
+        let observed_element = this.observed_element.take();
+        let root = this.root.take();
+        let focused_element = this.focused_element.take();
+        let keyboard_navigation = this.keyboard_navigation.take();
+        let get_window = this.get_window.clone();
+        let entries = std::mem::replace(&mut this.storage, TabsterCoreStorage::new()).values();
+
+        // The APIs and the parts borrow the core themselves.
+        drop(this);
+
+        if let Some(internal) = internal {
+            internal.borrow_mut().stop_observer();
+        }
+
+        if let Some(groupper) = groupper {
+            groupper.borrow_mut().dispose();
+        }
+        if let Some(mover) = mover {
+            mover.borrow_mut().dispose();
+        }
+        if let Some(modalizer) = modalizer {
+            modalizer.borrow_mut().dispose();
+        }
+        if let Some(deloser) = deloser {
+            deloser.borrow_mut().dispose();
+        }
+        if let Some(restorer) = restorer {
+            restorer.borrow_mut().dispose();
+        }
+        if let Some(outline) = outline {
+            outline.borrow_mut().dispose();
+        }
+        if let Some(observed_element) = observed_element {
+            observed_element.borrow_mut().dispose();
+        }
+        if let Some(root) = root {
+            root.borrow_mut().dispose();
+        }
+
+        for entry in entries {
+            dispose_parts(&entry);
+        }
+
+        if let Some(mut focused_element) = focused_element {
+            focused_element.dispose();
+        }
+        if let Some(keyboard_navigation) = keyboard_navigation {
+            keyboard_navigation.dispose();
+        }
+
+        dispose_instance_context(&get_window);
+    }
+
     pub(crate) fn queue_init(&mut self, callback: impl FnOnce() + 'static) {
         let Some(win) = self.win.as_ref() else {
             return;
@@ -193,24 +367,35 @@ impl TabsterCore {
         }
     }
 
-    fn drain_init_queue_fn(&self) -> Box<dyn Fn()> {
+    pub(crate) fn drain_init_queue_fn(&self) -> Box<dyn Fn()> {
         let init_queue = self.init_queue.clone();
+        let has_win = self.win.is_some();
         Box::new(move || {
-            let mut init_queue = init_queue.try_borrow_mut().unwrap_throw();
-            let queue = init_queue.drain(..);
+            if !has_win {
+                return;
+            }
+
+            // Resetting the queue before calling the callbacks to avoid recursion.
+            let queue: Vec<_> = init_queue
+                .try_borrow_mut()
+                .unwrap_throw()
+                .drain(..)
+                .collect();
             queue.into_iter().for_each(|callback| callback());
         })
     }
 
-    pub fn drain_init_queue(&mut self) {
-        if self.win.is_none() {
-            return;
-        }
+    /// The callbacks borrow the core themselves, so the core must not be
+    /// borrowed while the queue is drained.
+    pub fn drain_init_queue(tabster: &Arc<RefCell<Self>>) {
+        let drain_init_queue_fn = tabster.borrow().drain_init_queue_fn();
+        drain_init_queue_fn();
+    }
 
-        let mut init_queue = self.init_queue.try_borrow_mut().unwrap_throw();
-        // Resetting the queue before calling the callbacks to avoid recursion.
-        let queue = init_queue.drain(..);
-        queue.into_iter().for_each(|callback| callback());
+    /// The number of elements with Tabster data, lets the tests make sure
+    /// nothing is leaked.
+    pub fn storage_entries_count(&self) -> usize {
+        self.storage.len()
     }
 
     pub fn storage_entry(
@@ -239,16 +424,162 @@ impl TabsterCore {
 /// @param tabster Tabster instance
 pub fn get_groupper(tabster: &Tabster) -> Arc<RefCell<GroupperAPI>> {
     let tabster_core = tabster.core.clone();
+    let (groupper, get_window) = {
+        let tabster_core_ref = tabster_core.try_borrow().unwrap_throw();
+        (
+            tabster_core_ref.groupper.clone(),
+            tabster_core_ref.get_window.clone(),
+        )
+    };
+
+    let groupper = if let Some(groupper) = groupper {
+        groupper
+    } else {
+        Arc::new(RefCell::new(GroupperAPI::new(
+            tabster_core.clone(),
+            get_window,
+        )))
+    };
+
     let mut tabster_core_ref = tabster_core.try_borrow_mut().unwrap_throw();
+    tabster_core_ref.groupper = Some(groupper.clone());
+
+    groupper
+}
+
+/// Creates a new modalizer instance or returns an existing one
+/// @param tabster Tabster instance
+pub fn get_modalizer(tabster: &Tabster) -> Arc<RefCell<ModalizerAPI>> {
+    let tabster_core = tabster.core.clone();
+    let (modalizer, get_window) = {
+        let tabster_core_ref = tabster_core.try_borrow().unwrap_throw();
+        (
+            tabster_core_ref.modalizer.clone(),
+            tabster_core_ref.get_window.clone(),
+        )
+    };
 
-    if tabster_core_ref.groupper.is_none() {
-        tabster_core_ref.groupper = Some(Arc::new(RefCell::new(GroupperAPI::new(
+    let modalizer = if let Some(modalizer) = modalizer {
+        modalizer
+    } else {
+        Arc::new(RefCell::new(ModalizerAPI::new(
             tabster_core.clone(),
+            get_window,
+        )))
+    };
+
+    let mut tabster_core_ref = tabster_core.try_borrow_mut().unwrap_throw();
+    tabster_core_ref.modalizer = Some(modalizer.clone());
+
+    modalizer
+}
+
+/// Creates a new deloser instance or returns an existing one
+/// @param tabster Tabster instance
+pub fn get_deloser(tabster: &Tabster) -> Arc<RefCell<DeloserAPI>> {
+    let tabster_core = tabster.core.clone();
+    let (deloser, get_window) = {
+        let tabster_core_ref = tabster_core.try_borrow().unwrap_throw();
+        (
+            tabster_core_ref.deloser.clone(),
             tabster_core_ref.get_window.clone(),
-        ))));
-    }
+        )
+    };
+
+    let deloser = if let Some(deloser) = deloser {
+        deloser
+    } else {
+        Arc::new(RefCell::new(DeloserAPI::new(
+            tabster_core.clone(),
+            get_window,
+        )))
+    };
+
+    let mut tabster_core_ref = tabster_core.try_borrow_mut().unwrap_throw();
+    tabster_core_ref.deloser = Some(deloser.clone());
+
+    deloser
+}
+
+/// Creates a new restorer instance or returns an existing one
+/// @param tabster Tabster instance
+pub fn get_restorer(tabster: &Tabster) -> Arc<RefCell<RestorerAPI>> {
+    let tabster_core = tabster.core.clone();
+    let (restorer, get_window) = {
+        let tabster_core_ref = tabster_core.try_borrow().unwrap_throw();
+        (
+            tabster_core_ref.restorer.clone(),
+            tabster_core_ref.get_window.clone(),
+        )
+    };
+
+    let restorer = if let Some(restorer) = restorer {
+        restorer
+    } else {
+        Arc::new(RefCell::new(RestorerAPI::new(
+            tabster_core.clone(),
+            get_window,
+        )))
+    };
+
+    let mut tabster_core_ref = tabster_core.try_borrow_mut().unwrap_throw();
+    tabster_core_ref.restorer = Some(restorer.clone());
+
+    restorer
+}
+
+/// Creates a new outline instance or returns an existing one
+/// @param tabster Tabster instance
+pub fn get_outline(tabster: &Tabster) -> Arc<RefCell<OutlineAPI>> {
+    let tabster_core = tabster.core.clone();
+    let (outline, get_window) = {
+        let tabster_core_ref = tabster_core.try_borrow().unwrap_throw();
+        (
+            tabster_core_ref.outline.clone(),
+            tabster_core_ref.get_window.clone(),
+        )
+    };
+
+    let outline = if let Some(outline) = outline {
+        outline
+    } else {
+        Arc::new(RefCell::new(OutlineAPI::new(
+            tabster_core.clone(),
+            get_window,
+        )))
+    };
+
+    let mut tabster_core_ref = tabster_core.try_borrow_mut().unwrap_throw();
+    tabster_core_ref.outline = Some(outline.clone());
+
+    outline
+}
+
+/// Creates a new observed element instance or returns an existing one
+/// @param tabster Tabster instance
+pub fn get_observed_element(tabster: &Tabster) -> Arc<RefCell<ObservedElementAPI>> {
+    let tabster_core = tabster.core.clone();
+    let (observed_element, get_window) = {
+        let tabster_core_ref = tabster_core.try_borrow().unwrap_throw();
+        (
+            tabster_core_ref.observed_element.clone(),
+            tabster_core_ref.get_window.clone(),
+        )
+    };
+
+    let observed_element = if let Some(observed_element) = observed_element {
+        observed_element
+    } else {
+        Arc::new(RefCell::new(ObservedElementAPI::new(
+            tabster_core.clone(),
+            get_window,
+        )))
+    };
+
+    let mut tabster_core_ref = tabster_core.try_borrow_mut().unwrap_throw();
+    tabster_core_ref.observed_element = Some(observed_element.clone());
 
-    tabster_core_ref.groupper.clone().unwrap_throw()
+    observed_element
 }
 
 /// Creates a new mover instance or returns an existing one
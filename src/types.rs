@@ -1,60 +1,136 @@
 use crate::{
+    consts::keys,
+    deloser::ArcCellDeloser,
     groupper::Groupper,
     modalizer::{ArcCellModalizer, Modalizer},
     mover::Mover,
     mutation_event::observe_mutations,
+    restorer::ArcCellRestorer,
     root::ArcCellRoot,
     tabster::TabsterCore,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
 use std::{cell::RefCell, collections::HashMap, sync::Arc};
 use web_sys::{
     wasm_bindgen::UnwrapThrowExt, Document, Element, HtmlElement, HtmlInputElement, KeyboardEvent,
-    MutationObserver, MutationRecord, Node, NodeFilter, NodeList, TreeWalker, Window,
+    MutationObserver, MutationRecord, Node, NodeList, Window,
 };
 
-#[derive(Debug, Default, Clone)]
+/// The keys are named after `KeyboardEvent.key`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(rename_all = "PascalCase")]
 pub struct IgnoreKeydown {
-    tab: Option<bool>,
-    // Escape?: boolean;
-    // Enter?: boolean;
-    // ArrowUp?: boolean;
-    // ArrowDown?: boolean;
-    // ArrowLeft?: boolean;
-    // ArrowRight?: boolean;
-    // PageUp?: boolean;
-    // PageDown?: boolean;
-    // Home?: boolean;
-    // End?: boolean;
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escape: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enter: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrow_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrow_down: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrow_left: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrow_right: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_down: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub home: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<bool>,
 }
 
 impl IgnoreKeydown {
     pub fn get(&self, key: &str) -> Option<bool> {
         match key {
-            "tab" => self.tab,
+            keys::TAB => self.tab,
+            keys::ESCAPE => self.escape,
+            keys::ENTER => self.enter,
+            keys::ARROW_UP => self.arrow_up,
+            keys::ARROW_DOWN => self.arrow_down,
+            keys::ARROW_LEFT => self.arrow_left,
+            keys::ARROW_RIGHT => self.arrow_right,
+            keys::PAGE_UP => self.page_up,
+            keys::PAGE_DOWN => self.page_down,
+            keys::HOME => self.home,
+            keys::END => self.end,
             _ => None,
         }
     }
 
     pub fn assign(&mut self, other: Self) {
-        let Self { tab } = other;
+        let Self {
+            tab,
+            escape,
+            enter,
+            arrow_up,
+            arrow_down,
+            arrow_left,
+            arrow_right,
+            page_up,
+            page_down,
+            home,
+            end,
+        } = other;
         if tab.is_some() {
             self.tab = tab;
         }
+        if escape.is_some() {
+            self.escape = escape;
+        }
+        if enter.is_some() {
+            self.enter = enter;
+        }
+        if arrow_up.is_some() {
+            self.arrow_up = arrow_up;
+        }
+        if arrow_down.is_some() {
+            self.arrow_down = arrow_down;
+        }
+        if arrow_left.is_some() {
+            self.arrow_left = arrow_left;
+        }
+        if arrow_right.is_some() {
+            self.arrow_right = arrow_right;
+        }
+        if page_up.is_some() {
+            self.page_up = page_up;
+        }
+        if page_down.is_some() {
+            self.page_down = page_down;
+        }
+        if home.is_some() {
+            self.home = home;
+        }
+        if end.is_some() {
+            self.end = end;
+        }
     }
 }
 
-#[derive(Default)]
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct FocusableProps {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub is_default: Option<bool>,
     /// Do not determine an element's focusability based on aria-disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ignore_aria_disabled: Option<bool>,
     /// Exclude element (and all subelements) from Mover navigation.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub exclude_from_mover: Option<bool>,
     /// Prevents tabster from handling the keydown event
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub ignore_keydown: Option<IgnoreKeydown>,
 }
 
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct UncontrolledProps {
     // Normally, even uncontrolled areas should not be completely uncontrolled
     // to be able to interact with the rest of the application properly.
@@ -71,7 +147,8 @@ pub struct UncontrolledProps {
     // uncontrolled area.
     // WARNING: Use with caution, as it might break the normal keyboard navigation
     // between the uncontrolled area and the rest of the application.
-    completely: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completely: Option<bool>,
 }
 
 #[derive(Debug, Default)]
@@ -106,6 +183,7 @@ pub struct TabsterContext {
 pub type SysDummyInputsPosition = u8;
 
 #[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct SysProps {
     /// Force dummy input position outside or inside of the element.
     /// By default (when undefined), the position is determined dynamically
@@ -113,6 +191,11 @@ pub struct SysProps {
     /// plus a default Groupper/Mover/Modalizer implementation position).
     /// Setting to true will force the dummy inputs to be always outside of the element,
     /// setting to false will force the dummy inputs to be always inside.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_enum_value::<_, 2>"
+    )]
     pub dummy_inputs_position: Option<SysDummyInputsPosition>,
 }
 
@@ -122,6 +205,29 @@ pub type MoverDirection = u8;
 /// 0 | 1 | 2
 pub type Visibility = u8;
 
+/// 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8
+pub type MoverKey = u8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusSource {
+    Keyboard,
+    Mouse,
+    Programmatic,
+}
+
+#[derive(Debug, Clone)]
+pub struct FocusedElementDetail {
+    pub related_target: Option<HtmlElement>,
+    pub source: FocusSource,
+    pub modalizer_id: Option<String>,
+}
+
+impl FocusedElementDetail {
+    pub fn is_focused_programmatically(&self) -> bool {
+        self.source == FocusSource::Programmatic
+    }
+}
+
 pub struct NextTabbable {
     pub element: Option<HtmlElement>,
     pub uncontrolled: Option<HtmlElement>,
@@ -131,7 +237,11 @@ pub struct NextTabbable {
 #[derive(Serialize, Deserialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MoverProps {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_enum_value::<_, 4>"
+    )]
     pub direction: Option<MoverDirection>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memorize_current: Option<bool>,
@@ -152,7 +262,11 @@ pub struct MoverProps {
     /// uses the visibility part of the trackState prop to be able to
     /// go to first/last visible element (instead of first/last focusable
     /// element in DOM) when tabbing from outside of the mover.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_enum_value::<_, 2>"
+    )]
     pub visibility_aware: Option<Visibility>,
     /// When true, Mover will try to locate a focusable with Focusable.isDefault
     /// property as a prioritized element to focus. True by default.
@@ -164,12 +278,66 @@ pub struct MoverProps {
     /// an element of height 100px has 10px that are above the viewport
     /// hidden by scroll. This element is a valid visible element to focus.
     /// @default 0.8
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_optional_js_number"
+    )]
     pub visibility_tolerance: Option<f32>,
 }
+
+/// The state of a focusable element inside a Mover with `track_state`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MoverElementState {
+    pub is_current: bool,
+    /// The element goes right before the current one.
+    pub is_previous: bool,
+    /// The element goes right after the current one.
+    pub is_next: bool,
+    pub is_first: bool,
+    pub is_last: bool,
+    pub visibility: Visibility,
+    /// Zero-based position among the focusable elements of the Mover.
+    pub index: usize,
+    /// The number of the focusable elements in the Mover.
+    pub count: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MoverMoveFocusEventDetail {
+    pub key: MoverKey,
+}
+
+/// 1 | 2
+pub type GroupperMoveFocusAction = u8;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GroupperMoveFocusEventDetail {
+    pub action: GroupperMoveFocusAction,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GroupperActiveEventDetail {
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ModalizerProps {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_others_accessible: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub is_always_accessible: Option<bool>,
+    /// Do not focus the first focusable element when the modalizer gets active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_no_focus_first: Option<bool>,
+    /// Do not focus the default (`focusable: { isDefault: true }`) element when
+    /// the modalizer gets active.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_no_focus_default: Option<bool>,
     /// A focus trap variant, keeps focus inside the modal when tabbing
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub is_trapped: Option<bool>,
 }
 
@@ -267,15 +435,15 @@ impl From<FindAllProps> for FindFocusableProps {
     fn from(value: FindAllProps) -> Self {
         Self {
             container: value.container,
-            current_element: None,
+            current_element: value.current_element,
             reference_element: None,
             include_programmatically_focusable: None,
             ignore_accessibility: None,
-            use_active_modalizer: None,
+            use_active_modalizer: value.use_active_modalizer,
             modalizer_id: None,
-            is_backward: None,
+            is_backward: value.is_backward,
             accept_condition: None,
-            on_element: None,
+            on_element: value.on_element,
         }
     }
 }
@@ -321,21 +489,67 @@ pub struct FindDefaultProps {
 
 pub struct FindAllProps {
     /// The container used for the search.
-    container: HtmlElement,
+    pub container: HtmlElement,
+    /// The elemet to start from.
+    pub current_element: Option<HtmlElement>,
+    /// If true, find all elements before the current element instead of after it.
+    pub is_backward: Option<bool>,
+    /// Take active modalizer into account when searching for elements
+    /// (the elements out of active modalizer will not be returned).
+    pub use_active_modalizer: Option<bool>,
+    /// A callback that will be called for every focusable element found.
+    /// If false is returned from this callback, the search will stop.
+    pub on_element: Option<FindElementCallback>,
+}
+
+/// 0 | 1 | 2 | 3 | 4
+pub type RestoreFocusOrder = u8;
+
+/// 0 | 1 | 2
+pub type ObservedElementAccessibility = u8;
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ObservedElementProps {
+    /// The names to request the element by, several elements might share a name.
+    pub names: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
 }
 
+/// 0 | 1
+pub type RestorerType = u8;
+
 #[derive(Serialize, Deserialize, Clone)]
-pub struct RestoreFocusOrder {
-    history: u32,
-    deloser_default: u32,
-    root_default: u32,
-    deloser_first: u32,
-    root_first: u32,
+pub struct RestorerProps {
+    /// A source (i.e. a popup) asks to restore the focus when it loses it,
+    /// the most recently focused target (i.e. the popup trigger) receives it.
+    #[serde(deserialize_with = "deserialize_enum_value::<_, 1>")]
+    pub r#type: RestorerType,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct RootProps {
-    restore_focus_order: Option<RestoreFocusOrder>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_enum_value::<_, 4>"
+    )]
+    pub restore_focus_order: Option<RestoreFocusOrder>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeloserProps {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_enum_value::<_, 4>"
+    )]
+    pub restore_focus_order: Option<RestoreFocusOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_selector_check: Option<bool>,
 }
 
 pub struct TabsterProps {
@@ -362,17 +576,19 @@ pub trait DOMAPI {
         callback: impl Fn(Vec<MutationRecord>, MutationObserver) + 'static,
     ) -> MutationObserver;
 
-    fn create_tree_walker(
-        doc: &Document,
-        root: &Node,
-        what_to_show: u32,
-        filter: Option<&NodeFilter>,
-    ) -> TreeWalker;
+    /// `document.activeElement`, looking inside the open shadow roots.
+    fn get_active_element(doc: &Document) -> Option<Element>;
 
     fn get_parent_node(node: Option<Node>) -> Option<ParentNode>;
 
+    /// The parent node, or the host for the top level nodes of a shadow root.
+    fn get_composed_parent_node(node: Option<Node>) -> Option<ParentNode>;
+
+    /// The parent element, the host for the top level elements of a shadow root.
     fn get_parent_element(element: Option<HtmlElement>) -> Option<HtmlElement>;
 
+    /// Whether the child is inside the parent, including the open shadow roots
+    /// of the parent's descendants.
     fn node_contains(parent: Option<Node>, child: Option<Node>) -> bool;
 
     fn get_first_child(node: Option<Node>) -> Option<Node>;
@@ -394,6 +610,152 @@ pub trait DOMAPI {
     fn get_elements_by_name(reference_element: &HtmlElement, name: &str) -> NodeList;
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TabsterDOMRect {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl TabsterDOMRect {
+    pub fn width(&self) -> f64 {
+        self.right - self.left
+    }
+
+    pub fn height(&self) -> f64 {
+        self.bottom - self.top
+    }
+}
+
+/// The DOM the engine walks, measures and focuses. `WebDOM` is the browser
+/// one, `MemoryDOM` lets the focusability rules, the element walking and the
+/// tab order be tested natively. The Tabster parts still subscribe to the
+/// `web_sys` events and keep `web_sys` elements.
+pub trait DOMBackend {
+    type Element: Clone + PartialEq;
+
+    /// The `<body>`.
+    fn body(&self) -> Option<Self::Element>;
+
+    /// The parent in the flat tree: the slot for a slotted element, the host
+    /// for a shadow root child.
+    fn get_parent_element(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    /// The first element child in the flat tree, see `ElementTreeWalker`.
+    fn get_first_child(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    fn get_last_child(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    fn get_next_sibling(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    fn get_previous_sibling(&self, element: &Self::Element) -> Option<Self::Element>;
+
+    /// Whether the element is the ancestor or one of its flat tree descendants.
+    fn contains(&self, ancestor: &Self::Element, element: &Self::Element) -> bool {
+        let mut el = Some(element.clone());
+
+        while let Some(el_ref) = el {
+            if el_ref == *ancestor {
+                return true;
+            }
+
+            el = self.get_parent_element(&el_ref);
+        }
+
+        false
+    }
+
+    /// The focused element, inside the open shadow roots.
+    fn get_active_element(&self) -> Option<Self::Element>;
+
+    fn focus(&self, element: &Self::Element);
+
+    fn get_attribute(&self, element: &Self::Element, name: &str) -> Option<String>;
+
+    fn has_attribute(&self, element: &Self::Element, name: &str) -> bool {
+        self.get_attribute(element, name).is_some()
+    }
+
+    /// Whether the element matches `FOCUSABLE_SELECTOR`.
+    fn matches_focusable_selector(&self, element: &Self::Element) -> bool;
+
+    /// `HTMLElement.tabIndex`.
+    fn tab_index(&self, element: &Self::Element) -> i32;
+
+    /// Hosts which delegate focus forward it to their first focusable
+    /// descendant, the host itself is never a tab stop.
+    fn is_delegates_focus_host(&self, _element: &Self::Element) -> bool {
+        false
+    }
+
+    fn get_bounding_client_rect(&self, element: &Self::Element) -> TabsterDOMRect;
+
+    /// Whether the element is not rendered: `display: none` on it or its
+    /// ancestors, or `visibility: hidden`.
+    fn is_display_none(&self, element: &Self::Element) -> bool;
+
+    /// Whether the `aria-hidden` of the element is set by the modalizer,
+    /// which doesn't make the element inaccessible.
+    fn is_augmented(&self, _element: &Self::Element) -> bool {
+        false
+    }
+
+    /// Whether the element has the `ignore_aria_disabled` focusable prop.
+    fn ignore_aria_disabled(&self, _element: &Self::Element) -> bool {
+        false
+    }
+
+    fn is_focusable(
+        &self,
+        element: &Self::Element,
+        include_programmatically_focusable: Option<bool>,
+        no_visible_check: Option<bool>,
+        no_accessible_check: Option<bool>,
+    ) -> bool {
+        self.matches_focusable_selector(element)
+            && !self.is_delegates_focus_host(element)
+            && (include_programmatically_focusable.unwrap_or_default()
+                || self.tab_index(element) != -1)
+            && (no_visible_check.unwrap_or_default() || self.is_visible(element))
+            && (no_accessible_check.unwrap_or_default() || self.is_accessible(element))
+    }
+
+    fn is_visible(&self, element: &Self::Element) -> bool {
+        if self.is_display_none(element) {
+            return false;
+        }
+
+        // This might happen, for example, if our <body> is in hidden <iframe>.
+        self.body().is_some_and(|body| {
+            let rect = self.get_bounding_client_rect(&body);
+            rect.width() != 0.0 || rect.height() != 0.0
+        })
+    }
+
+    fn is_accessible(&self, element: &Self::Element) -> bool {
+        let mut el = Some(element.clone());
+
+        while let Some(el_ref) = el {
+            let is_hidden = self
+                .get_attribute(&el_ref, "aria-hidden")
+                .is_some_and(|value| value.to_lowercase() == "true");
+
+            if is_hidden && !self.is_augmented(&el_ref) {
+                return false;
+            }
+
+            if !self.ignore_aria_disabled(&el_ref) && self.has_attribute(&el_ref, "disabled") {
+                return false;
+            }
+
+            el = self.get_parent_element(&el_ref);
+        }
+
+        true
+    }
+}
+
 pub type GetWindow = Box<dyn Fn() -> Window>;
 
 #[derive(Clone)]
@@ -443,8 +805,11 @@ pub struct TabsterOnElement {
     pub mover: Option<Arc<RefCell<Mover>>>,
     pub groupper: Option<Arc<RefCell<Groupper>>>,
     pub modalizer: Option<ArcCellModalizer>,
+    pub deloser: Option<ArcCellDeloser>,
+    pub restorer: Option<ArcCellRestorer>,
     pub focusable: Option<Arc<FocusableProps>>,
     pub uncontrolled: Option<UncontrolledProps>,
+    pub observed: Option<ObservedElementProps>,
 }
 
 impl TabsterOnElement {
@@ -452,8 +817,11 @@ impl TabsterOnElement {
         self.mover.is_none()
             && self.groupper.is_none()
             && self.modalizer.is_none()
+            && self.deloser.is_none()
+            && self.restorer.is_none()
             && self.focusable.is_none()
             && self.uncontrolled.is_none()
+            && self.observed.is_none()
     }
 }
 
@@ -511,36 +879,179 @@ impl InternalAPI {
 pub type GroupperTabbability = u8;
 
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct GroupperProps {
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_enum_value::<_, 2>"
+    )]
     pub tabbability: Option<GroupperTabbability>,
-    delegated: Option<bool>, // This allows to tweak the groupper behaviour for the cases when
-                             // the groupper container is not focusable and groupper has Limited or LimitedTrapFocus
-                             // tabbability. By default, the groupper will automatically become active once the focus
-                             // goes to first focusable element inside the groupper during tabbing. When true, the
-                             // groupper will become active only after Enter is pressed on first focusable element
-                             // inside the groupper.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delegated: Option<bool>, // This allows to tweak the groupper behaviour for the cases when
+                                 // the groupper container is not focusable and groupper has Limited or LimitedTrapFocus
+                                 // tabbability. By default, the groupper will automatically become active once the focus
+                                 // goes to first focusable element inside the groupper during tabbing. When true, the
+                                 // groupper will become active only after Enter is pressed on first focusable element
+                                 // inside the groupper.
 }
 
+/// The `data-tabster` attribute value. A parsed value is written back in its
+/// own key order and with its unknown keys, so the attributes written by the
+/// JS side are kept as they are.
 #[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(remote = "Self")]
 pub struct TabsterAttributeProps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deloser: Option<DeloserProps>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root: Option<RootProps>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub uncontrolled: Option<UncontrolledProps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modalizer: Option<ModalizerProps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focusable: Option<FocusableProps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub groupper: Option<GroupperProps>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub mover: Option<MoverProps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observed: Option<ObservedElementProps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sys: Option<SysProps>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mover: Option<MoverProps>,
+    pub restorer: Option<RestorerProps>,
+    /// The JSON the props are parsed from.
+    #[serde(skip)]
+    source: Option<Value>,
+}
+
+impl<'de> Deserialize<'de> for TabsterAttributeProps {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = Value::deserialize(deserializer)?;
+        let mut props = TabsterAttributeProps::deserialize(&source).map_err(de::Error::custom)?;
+        props.source = Some(source);
+
+        Ok(props)
+    }
+}
+
+impl Serialize for TabsterAttributeProps {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let to_value = |props: &Self| {
+            TabsterAttributeProps::serialize(props, serde_json::value::Serializer)
+                .map_err(ser::Error::custom)
+        };
+        let value = to_value(self)?;
+
+        let Some(source) = self.source.as_ref() else {
+            return value.serialize(serializer);
+        };
+
+        let known = TabsterAttributeProps::deserialize(source)
+            .map_err(ser::Error::custom)
+            .and_then(|props| to_value(&props))?;
+
+        keep_source_format(source, &known, value).serialize(serializer)
+    }
+}
+
+/// `value` in the key order and the number format of `source`. The keys of
+/// `source` which the typed props don't know (missing in `known`, which is
+/// `source` parsed and written back), e.g. upstream additions and `null`s,
+/// are kept too.
+fn keep_source_format(source: &Value, known: &Value, value: Value) -> Value {
+    match (source, value) {
+        (Value::Object(source), Value::Object(mut value)) => {
+            let known = known.as_object();
+            let mut result = Map::new();
+
+            for (key, source_value) in source {
+                let known_value = known.and_then(|known| known.get(key));
+
+                if let Some(value) = value.shift_remove(key) {
+                    result.insert(
+                        key.clone(),
+                        keep_source_format(
+                            source_value,
+                            known_value.unwrap_or(&Value::Null),
+                            value,
+                        ),
+                    );
+                } else if known_value.is_none() {
+                    result.insert(key.clone(), source_value.clone());
+                }
+            }
+
+            // The new keys go last, as with the JS objects.
+            result.extend(value);
+
+            Value::Object(result)
+        }
+        (source, value) if *known == value => source.clone(),
+        (_, value) => value,
+    }
 }
 
 impl TabsterAttributeProps {
     pub fn json_string(self) -> String {
-        String::new()
+        serde_json::to_string(&self).unwrap_throw()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.mover.is_none() && self.groupper.is_none() && self.sys.is_none()
+        self.mover.is_none()
+            && self.groupper.is_none()
+            && self.modalizer.is_none()
+            && self.deloser.is_none()
+            && self.restorer.is_none()
+            && self.uncontrolled.is_none()
+            && self.focusable.is_none()
+            && self.observed.is_none()
+            && self.root.is_none()
+            && self.sys.is_none()
     }
 }
 
 pub type TabsterDOMAttribute = (String, String);
+
+/// Writes the numbers as `JSON.stringify()` does: `1` rather than `1.0`, and
+/// the shortest `f32` digits rather than the widened `f64` ones.
+fn serialize_optional_js_number<S: Serializer>(
+    value: &Option<f32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) if value.is_finite() && value.fract() == 0.0 => {
+            serializer.serialize_i64(*value as i64)
+        }
+        Some(value) => serializer.serialize_f64(value.to_string().parse().unwrap_or(*value as f64)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// The enum-like values are numbers from `0` to `MAX` on the wire.
+fn deserialize_enum_value<'de, D: Deserializer<'de>, const MAX: u8>(
+    deserializer: D,
+) -> Result<u8, D::Error> {
+    let value = u8::deserialize(deserializer)?;
+
+    if value > MAX {
+        return Err(serde::de::Error::custom(format!(
+            "invalid value: {value}, expected a value from 0 to {MAX}"
+        )));
+    }
+
+    Ok(value)
+}
+
+fn deserialize_optional_enum_value<'de, D: Deserializer<'de>, const MAX: u8>(
+    deserializer: D,
+) -> Result<Option<u8>, D::Error> {
+    #[derive(Deserialize)]
+    struct EnumValue<const MAX: u8>(
+        #[serde(deserialize_with = "deserialize_enum_value::<_, MAX>")] u8,
+    );
+
+    Ok(Option::<EnumValue<MAX>>::deserialize(deserializer)?.map(|EnumValue(value)| value))
+}
@@ -1,9 +1,10 @@
 use crate::{
     console_log,
     consts::TABSTER_DUMMY_INPUT_ATTRIBUTE_NAME,
-    dom_api::DOM,
+    dom_api::{WebDOM, DOM},
+    instance::get_tabster_on_element,
     tabster::TabsterCore,
-    types::{self, GetWindow, DOMAPI},
+    types::{self, DOMBackend, GetWindow, DOMAPI},
     web::set_timeout,
     SysDummyInputsPositions,
 };
@@ -11,99 +12,129 @@ use send_wrapper::SendWrapper;
 use std::{
     cell::RefCell,
     collections::HashMap,
-    ops::Deref,
+    marker::PhantomData,
     sync::{Arc, LazyLock, OnceLock, RwLock},
 };
 use web_sys::{
     js_sys::{self, Reflect, Uint32Array},
-    wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt},
-    Document, Element, HtmlElement, HtmlInputElement, Node, NodeFilter, TreeWalker, Window,
+    wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt},
+    AssignedNodesOptions, Element, HtmlElement, HtmlInputElement, HtmlSlotElement, ShadowRoot,
+    Window,
 };
 
-#[derive(Clone)]
-struct FakeWeakRef<T: DerefHtmlElement + Clone> {
-    target: Option<T>,
-}
-
-impl<T: DerefHtmlElement + Clone> FakeWeakRef<T> {
-    pub fn new(target: Option<T>) -> Self {
-        Self { target }
-    }
-}
-
-pub trait DerefHtmlElement {
-    fn deref(&self) -> Option<HtmlElement>;
-}
-
-impl<T: DerefHtmlElement + Clone> DerefHtmlElement for FakeWeakRef<T> {
-    fn deref(&self) -> Option<HtmlElement> {
-        if let Some(target) = &self.target {
-            target.deref()
-        } else {
-            None
-        }
-    }
-}
+/// How often the fallback registry drops the elements which have left the document.
+const ELEMENT_BY_UID_CLEANUP_INTERVAL: i32 = 2 * 60 * 1000; // 2 minutes.
 
-impl DerefHtmlElement for HtmlElement {
-    fn deref(&self) -> Option<HtmlElement> {
-        Some(self.clone())
-    }
+#[derive(Clone)]
+enum WeakElementRef {
+    WeakRef(js_sys::WeakRef),
+    /// There is no `WeakRef` in the window, the element is held by the uid
+    /// registry of the instance context until it leaves the document.
+    Uid(String, Arc<InstanceContext>),
 }
 
-pub struct WeakHTMLElement<T: DerefHtmlElement + Clone = HtmlElement, D = ()> {
-    weak_ref: RefCell<Option<FakeWeakRef<T>>>,
+pub struct WeakHTMLElement<T: JsCast + Clone = HtmlElement, D = ()> {
+    weak_ref: RefCell<Option<WeakElementRef>>,
     data: Option<D>,
+    _target: PhantomData<T>,
 }
 
-impl<T: DerefHtmlElement + Clone, D: Clone> Clone for WeakHTMLElement<T, D> {
+impl<T: JsCast + Clone, D: Clone> Clone for WeakHTMLElement<T, D> {
     fn clone(&self) -> Self {
         Self {
             weak_ref: self.weak_ref.clone(),
             data: self.data.clone(),
+            _target: PhantomData,
         }
     }
 }
 
-impl<T: DerefHtmlElement + Clone + 'static, D: Clone> WeakHTMLElement<T, D> {
+impl<T: JsCast + Clone, D: Clone> WeakHTMLElement<T, D> {
     pub fn new(get_window: Arc<GetWindow>, element: T, data: Option<D>) -> Self {
         let context = get_instance_context(&get_window);
+        let element: HtmlElement = element.unchecked_into();
 
-        let weak_ref = FakeWeakRef::new(Some(element));
-        context
-            .fake_weak_refs
-            .write()
-            .unwrap()
-            .push(SendWrapper::new(Box::new(weak_ref.clone())));
+        let weak_ref = if context.has_weak_ref {
+            WeakElementRef::WeakRef(js_sys::WeakRef::new(element.unchecked_ref()))
+        } else {
+            let uid = get_element_uid(&get_window, &element);
+            context
+                .element_by_uid
+                .write()
+                .unwrap_throw()
+                .insert(uid.clone(), SendWrapper::new(element));
+            start_element_by_uid_cleanup(&get_window, &context);
+
+            WeakElementRef::Uid(uid, context)
+        };
 
         Self {
             weak_ref: Some(weak_ref).into(),
             data,
+            _target: PhantomData,
         }
     }
 
+    /// The element, unless it is garbage-collected or not in the document.
     pub fn get(&self) -> Option<T> {
-        let mut element = None::<T>;
-
-        if let Some(weak_ref) = self.weak_ref.borrow().as_ref() {
-            element = weak_ref.target.clone();
+        let element = match self.weak_ref.borrow().as_ref()? {
+            WeakElementRef::WeakRef(weak_ref) => weak_ref.deref().map(JsCast::unchecked_into),
+            WeakElementRef::Uid(uid, context) => context
+                .element_by_uid
+                .read()
+                .unwrap_throw()
+                .get(uid)
+                .map(|element| HtmlElement::clone(element)),
+        };
 
-            if element.is_none() {
-                *self.weak_ref.borrow_mut() = None;
-            }
-        }
+        let Some(element) = element else {
+            // Gone for good.
+            *self.weak_ref.borrow_mut() = None;
+            return None;
+        };
 
-        element
+        element.is_connected().then(|| element.unchecked_into())
     }
 
-    fn get_data(&self) -> Option<D> {
+    pub fn get_data(&self) -> Option<D> {
         self.data.clone()
     }
 }
 
+/// Periodically drops the detached elements from the fallback registry, the
+/// elements which are never asked for again would be held forever otherwise.
+fn start_element_by_uid_cleanup(get_window: &Arc<GetWindow>, context: &Arc<InstanceContext>) {
+    let mut timer = context.element_by_uid_cleanup_timer.write().unwrap_throw();
+    if timer.is_some() {
+        return;
+    }
+
+    let context = context.clone();
+    *timer = Some(set_timeout(
+        &get_window(),
+        {
+            let get_window = get_window.clone();
+            move || {
+                *context.element_by_uid_cleanup_timer.write().unwrap_throw() = None;
+
+                let is_empty = {
+                    let mut element_by_uid = context.element_by_uid.write().unwrap_throw();
+                    element_by_uid.retain(|_, element| element.is_connected());
+                    element_by_uid.is_empty()
+                };
+
+                if !is_empty {
+                    start_element_by_uid_cleanup(&get_window, &context);
+                }
+            }
+        },
+        ELEMENT_BY_UID_CLEANUP_INTERVAL,
+    ));
+}
+
 pub fn should_ignore_focus(element: &Element) -> bool {
-    false
-    // return !!(element as FocusedElementWithIgnoreFlag).__shouldIgnoreFocus;
+    // Dummy inputs are the only elements with the ignore flag.
+    element.has_attribute(TABSTER_DUMMY_INPUT_ATTRIBUTE_NAME)
 }
 
 fn to_base36(num: u32) -> String {
@@ -273,6 +304,15 @@ impl DummyInputManager {
         self.on_focus_in = on_focus_in;
         self.on_focus_out = on_focus_out;
     }
+
+    pub fn dispose(&mut self) {
+        if let Some(instance) = self.instance.take() {
+            instance.borrow_mut().dispose();
+        }
+
+        self.on_focus_in = None;
+        self.on_focus_out = None;
+    }
 }
 
 struct DummyInputManagerCore {
@@ -344,6 +384,22 @@ impl DummyInputManagerCore {
         this
     }
 
+    fn dispose(&mut self) {
+        if let Some(timer) = self.add_timer.borrow_mut().take() {
+            (self.get_window)().clear_timeout_with_handle(timer);
+        }
+
+        if let Some(mut first_dummy) = self.first_dummy.take() {
+            first_dummy.dispose();
+        }
+
+        if let Some(mut last_dummy) = self.last_dummy.take() {
+            last_dummy.dispose();
+        }
+
+        self.element = None;
+    }
+
     /// Adds dummy inputs as the first and last child of the given element
     /// Called each time the children under the element is mutated
     fn add_dummy_inputs(this: Arc<RefCell<DummyInputManagerCore>>) {
@@ -361,8 +417,7 @@ impl DummyInputManagerCore {
                 let mut add_timer = add_timer.borrow_mut();
                 *add_timer = None;
 
-                let this = this_.borrow();
-                this.ensure_position();
+                this_.borrow_mut().ensure_position();
 
                 // if (__DEV__) {
                 //     this._firstDummy &&
@@ -378,15 +433,26 @@ impl DummyInputManagerCore {
         *add_timer_ref = Some(timer);
     }
 
-    fn ensure_position(&self) {
+    fn ensure_position(&mut self) {
+        self.insert_dummy_inputs();
+
+        for dummy in [&mut self.first_dummy, &mut self.last_dummy]
+            .into_iter()
+            .flatten()
+        {
+            dummy.on_added();
+        }
+    }
+
+    fn insert_dummy_inputs(&self) {
         let element = self.element.clone().map(|e| e.get()).flatten();
         let first_dummy_input = if let Some(first_dummy) = &self.first_dummy {
-            first_dummy.input.clone()
+            first_dummy.input()
         } else {
             None
         };
         let last_dummy_input = if let Some(last_dummy) = &self.last_dummy {
-            last_dummy.input.clone()
+            last_dummy.input()
         } else {
             None
         };
@@ -454,7 +520,11 @@ pub struct DummyInputProps {
 
 pub struct DummyInput {
     is_phantom: bool,
-    pub input: Option<HtmlElement>,
+    /// The new input is held until it is added to the DOM, which keeps it
+    /// alive from then on, the container must not be held by the input.
+    new_input: Option<HtmlElement>,
+    input: Option<WeakHTMLElement>,
+    get_window: Arc<GetWindow>,
     is_first: bool,
     pub is_outside: bool,
 }
@@ -513,55 +583,258 @@ impl DummyInput {
         }
 
         Self {
-            input: Some(input),
+            new_input: Some(input),
+            input: None,
+            get_window,
             is_first: props.is_first,
             is_phantom,
             is_outside,
         }
     }
-}
 
-pub fn create_element_tree_walker(
-    doc: &Document,
-    root: &Node,
-    accept_node: impl Fn(Node) -> u32 + 'static,
-) -> Option<TreeWalker> {
-    if root.node_type() != Node::ELEMENT_NODE {
-        return None;
+    pub fn input(&self) -> Option<HtmlElement> {
+        self.new_input
+            .clone()
+            .or_else(|| self.input.as_ref().and_then(|input| input.get()))
     }
-    let node_filter = NodeFilter::new();
-    let cb: Closure<dyn Fn(Node) -> u32> = Closure::new(accept_node);
-    let cb = cb.into_js_value();
-    node_filter.set_accept_node(cb.as_ref().unchecked_ref());
 
-    Some(DOM::create_tree_walker(
-        doc,
-        root,
-        *NodeFilterEnum::ShowElement,
-        Some(&node_filter),
-    ))
+    /// Holds the input weakly once it is in the DOM.
+    fn on_added(&mut self) {
+        let Some(input) = self.new_input.take_if(|input| input.is_connected()) else {
+            return;
+        };
+
+        self.input = Some(WeakHTMLElement::new(self.get_window.clone(), input, None));
+    }
+
+    /// Removes the input from the DOM.
+    pub fn dispose(&mut self) {
+        if let Some(input) = self.input() {
+            input.remove();
+        }
+
+        self.new_input = None;
+        self.input = None;
+    }
 }
 
-pub enum NodeFilterEnum {
-    FilterAccept,
-    FilterReject,
-    FilterSkip,
-    ShowElement,
+type AcceptNode<'a, E> = Box<dyn Fn(&E) -> FilterResult + 'a>;
+
+/// Walks the elements like a `TreeWalker` with `SHOW_ELEMENT` does, but in
+/// the flat tree of the DOM backend. In the browser the children of a host
+/// with an open shadow root are the shadow root content, and the children of
+/// a `<slot>` are its flattened assigned elements. The light DOM children of
+/// such a host are only walked where they are slotted.
+pub struct ElementTreeWalker<'a, D: DOMBackend = WebDOM> {
+    dom: &'a D,
+    root: D::Element,
+    current_node: D::Element,
+    accept_node: AcceptNode<'a, D::Element>,
 }
 
-impl Deref for NodeFilterEnum {
-    type Target = u32;
+impl<D: DOMBackend> ElementTreeWalker<'_, D> {
+    pub fn current_node(&self) -> D::Element {
+        self.current_node.clone()
+    }
+
+    pub fn set_current_node(&mut self, node: &D::Element) {
+        self.current_node = node.clone();
+    }
+
+    pub fn next_node(&mut self) -> Option<D::Element> {
+        let mut node = self.current_node.clone();
+        let mut result = FilterResult::Accept;
+
+        loop {
+            while result != FilterResult::Reject {
+                let Some(child) = self.dom.get_first_child(&node) else {
+                    break;
+                };
+
+                node = child;
+                result = (self.accept_node)(&node);
+
+                if result == FilterResult::Accept {
+                    self.current_node = node.clone();
+                    return Some(node);
+                }
+            }
+
+            loop {
+                if node == self.root {
+                    return None;
+                }
+
+                if let Some(sibling) = self.dom.get_next_sibling(&node) {
+                    node = sibling;
+                    break;
+                }
+
+                node = self.dom.get_parent_element(&node)?;
+            }
+
+            result = (self.accept_node)(&node);
+
+            if result == FilterResult::Accept {
+                self.current_node = node.clone();
+                return Some(node);
+            }
+        }
+    }
+
+    pub fn previous_node(&mut self) -> Option<D::Element> {
+        let mut node = self.current_node.clone();
+
+        while node != self.root {
+            while let Some(sibling) = self.dom.get_previous_sibling(&node) {
+                node = sibling;
+                let mut result = (self.accept_node)(&node);
+
+                while result != FilterResult::Reject {
+                    let Some(child) = self.dom.get_last_child(&node) else {
+                        break;
+                    };
+
+                    node = child;
+                    result = (self.accept_node)(&node);
+                }
+
+                if result == FilterResult::Accept {
+                    self.current_node = node.clone();
+                    return Some(node);
+                }
+            }
+
+            // Like in `TreeWalker`, the root itself is visited too.
+            node = self.dom.get_parent_element(&node)?;
 
-    fn deref(&self) -> &Self::Target {
-        match self {
-            Self::FilterAccept => &1,
-            Self::FilterReject => &2,
-            Self::FilterSkip => &3,
-            Self::ShowElement => &0x1,
+            if (self.accept_node)(&node) == FilterResult::Accept {
+                self.current_node = node.clone();
+                return Some(node);
+            }
         }
+
+        None
+    }
+}
+
+pub fn create_element_tree_walker<'a, D: DOMBackend>(
+    dom: &'a D,
+    root: &D::Element,
+    accept_node: impl Fn(&D::Element) -> FilterResult + 'a,
+) -> ElementTreeWalker<'a, D> {
+    ElementTreeWalker {
+        dom,
+        root: root.clone(),
+        current_node: root.clone(),
+        accept_node: Box::new(accept_node),
     }
 }
 
+/// The slot the element is rendered in. A slot which is itself slotted is
+/// replaced by its assigned elements, the outermost slot counts.
+fn composed_assigned_slot(element: &Element) -> Option<HtmlSlotElement> {
+    let mut slot = element.assigned_slot()?;
+
+    while let Some(outer_slot) = slot.assigned_slot() {
+        slot = outer_slot;
+    }
+
+    Some(slot)
+}
+
+/// `slot.assignedElements({ flatten: true })`, the fallback content of the
+/// slot when nothing is assigned.
+fn slot_assigned_elements(slot: &HtmlSlotElement) -> Vec<Element> {
+    let options = AssignedNodesOptions::new();
+    options.set_flatten(true);
+
+    slot.assigned_nodes_with_options(&options)
+        .into_iter()
+        .filter_map(|node| node.dyn_into::<Element>().ok())
+        .collect()
+}
+
+/// The `<slot>` of a shadow tree, the slots elsewhere are plain elements.
+fn as_shadow_slot(element: &Element) -> Option<&HtmlSlotElement> {
+    element
+        .dyn_ref::<HtmlSlotElement>()
+        .filter(|slot| slot.get_root_node().is_instance_of::<ShadowRoot>())
+}
+
+pub(crate) fn composed_parent(element: &Element) -> Option<Element> {
+    if let Some(slot) = composed_assigned_slot(element) {
+        return Some(slot.into());
+    }
+
+    DOM::get_composed_parent_node(Some(element.clone().into()))?
+        .dyn_into()
+        .ok()
+}
+
+pub(crate) fn composed_first_child(element: &Element) -> Option<Element> {
+    if let Some(shadow_root) = element.shadow_root() {
+        return shadow_root.first_element_child();
+    }
+
+    if let Some(slot) = as_shadow_slot(element) {
+        return slot_assigned_elements(slot).into_iter().next();
+    }
+
+    element.first_element_child()
+}
+
+pub(crate) fn composed_last_child(element: &Element) -> Option<Element> {
+    if let Some(shadow_root) = element.shadow_root() {
+        return shadow_root.last_element_child();
+    }
+
+    if let Some(slot) = as_shadow_slot(element) {
+        return slot_assigned_elements(slot).pop();
+    }
+
+    element.last_element_child()
+}
+
+/// The sibling of a slotted element is the adjacent one in its slot, which
+/// is not necessarily its light DOM sibling.
+pub(crate) fn composed_sibling(element: &Element, offset: isize) -> Option<Element> {
+    let Some(slot) = composed_assigned_slot(element) else {
+        return if offset > 0 {
+            element.next_element_sibling()
+        } else {
+            element.previous_element_sibling()
+        };
+    };
+
+    let assigned = slot_assigned_elements(&slot);
+    let index = assigned.iter().position(|el| el == element)?;
+
+    index
+        .checked_add_signed(offset)
+        .and_then(|index| assigned.get(index))
+        .cloned()
+}
+
+/// Hosts which delegate focus forward it to their first focusable descendant,
+/// the host itself is never a tab stop.
+pub fn is_delegates_focus_host(element: &Element) -> bool {
+    element.shadow_root().is_some_and(|shadow_root| {
+        Reflect::get(&shadow_root, &JsValue::from_str("delegatesFocus"))
+            .ok()
+            .and_then(|delegates_focus| delegates_focus.as_bool())
+            .unwrap_or_default()
+    })
+}
+
+/// What `ElementTreeWalker` does with a node, `Reject` skips its children as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterResult {
+    Accept,
+    Reject,
+    Skip,
+}
+
 pub fn get_adjacent_element(from: HtmlElement, prev: Option<bool>) -> Option<HtmlElement> {
     let mut cur = Some(from);
     let mut adjacent = None::<HtmlElement>;
@@ -587,19 +860,91 @@ pub fn get_adjacent_element(from: HtmlElement, prev: Option<bool>) -> Option<Htm
     adjacent
 }
 
-pub fn get_last_child(container: &HtmlElement) -> Option<HtmlElement> {
-    let mut last_child: Option<HtmlElement> = None;
-    let mut el = DOM::get_last_element_child(Some(container.clone().dyn_into().unwrap_throw()));
-    loop {
-        if el.is_none() {
-            break;
-        }
-        last_child = el.clone().map(|el| el.dyn_into().unwrap_throw());
-        el = DOM::get_last_element_child(el);
+/// The deepest last descendant of the container in the flat tree.
+pub fn get_last_child<D: DOMBackend>(dom: &D, container: &D::Element) -> Option<D::Element> {
+    let mut last_child = None;
+    let mut el = dom.get_last_child(container);
+    while let Some(el_ref) = el {
+        el = dom.get_last_child(&el_ref);
+        last_child = Some(el_ref);
     }
     last_child
 }
 
+pub fn get_scrollable_container(element: &HtmlElement) -> Option<HtmlElement> {
+    let doc = element.owner_document()?;
+
+    let mut el = DOM::get_parent_element(Some(element.clone()));
+    while let Some(e) = el {
+        if e.scroll_width() > e.client_width() || e.scroll_height() > e.client_height() {
+            return Some(e);
+        }
+        el = DOM::get_parent_element(Some(e));
+    }
+
+    doc.document_element().map(|e| e.dyn_into().unwrap_throw())
+}
+
+pub fn get_bounding_rect(element: &HtmlElement) -> types::TabsterDOMRect {
+    let Some(doc) = element.owner_document() else {
+        return Default::default();
+    };
+    let Some(scrolling_element) = doc.document_element() else {
+        return Default::default();
+    };
+
+    // A bounding rect of the top-level element contains the whole page regardless of the
+    // scrollbar. So, we improvise a little and limiting the final result...
+    let mut left = 0.0;
+    let mut top = 0.0;
+    let mut right = scrolling_element.client_width() as f64;
+    let mut bottom = scrolling_element.client_height() as f64;
+
+    if **element != scrolling_element {
+        let r = WebDOM::new(doc).get_bounding_client_rect(element);
+        left = f64::max(left, r.left);
+        top = f64::max(top, r.top);
+        right = f64::min(right, r.right);
+        bottom = f64::min(bottom, r.bottom);
+    }
+
+    let (left, right) = if left < right {
+        (left, right)
+    } else {
+        (-1.0, -1.0)
+    };
+    let (top, bottom) = if top < bottom {
+        (top, bottom)
+    } else {
+        (-1.0, -1.0)
+    };
+
+    types::TabsterDOMRect {
+        left,
+        top,
+        right,
+        bottom,
+    }
+}
+
+pub fn is_element_vertically_visible_in_container(element: &HtmlElement, tolerance: f32) -> bool {
+    let Some(container) = get_scrollable_container(element) else {
+        return true;
+    };
+    let Some(doc) = element.owner_document() else {
+        return true;
+    };
+
+    let container_rect = get_bounding_rect(&container);
+    let element_rect = WebDOM::new(doc).get_bounding_client_rect(element);
+    let intersection_tolerance = element_rect.height() * (1.0 - tolerance as f64);
+    let top_intersection = f64::max(0.0, container_rect.top - element_rect.top);
+    let bottom_intersection = f64::max(0.0, element_rect.bottom - container_rect.bottom);
+    let total_intersection = top_intersection + bottom_intersection;
+
+    total_intersection == 0.0 || total_intersection <= intersection_tolerance
+}
+
 static TABSTER_INSTANCE_CONTEXT: OnceLock<RwLock<HashMap<String, Arc<InstanceContext>>>> =
     OnceLock::new();
 
@@ -608,10 +953,11 @@ static TABSTER_INSTANCE_CONTEXT: OnceLock<RwLock<HashMap<String, Arc<InstanceCon
 // }
 
 pub struct InstanceContext {
-    pub element_by_uid: Arc<RwLock<HashMap<String, String>>>,
-    // elementByUId: { [uid: string]: WeakHTMLElement<HTMLElementWithUID> };
+    /// The elements held for `WeakHTMLElement` when there is no `WeakRef`.
+    pub element_by_uid: Arc<RwLock<HashMap<String, SendWrapper<HtmlElement>>>>,
+    element_by_uid_cleanup_timer: RwLock<Option<i32>>,
     // basics: InternalBasics,
-    // WeakRef?: WeakRefConstructor;
+    has_weak_ref: bool,
     // containerBoundingRectCache: {
     //     [id: string]: {
     //         rect: TabsterDOMRect;
@@ -620,9 +966,6 @@ pub struct InstanceContext {
     // };
     last_container_bounding_rect_cache_id: i32,
     container_bounding_rect_cache_timer: Option<i32>,
-    fake_weak_refs: RwLock<Vec<SendWrapper<Box<dyn DerefHtmlElement>>>>,
-    fake_weak_refs_timer: Option<i32>,
-    fake_weak_refs_started: bool,
 }
 
 pub fn get_instance_context(get_window: &Arc<GetWindow>) -> Arc<InstanceContext> {
@@ -656,17 +999,16 @@ pub fn get_instance_context(get_window: &Arc<GetWindow>) -> Arc<InstanceContext>
     // };
     let ctx = Arc::new(InstanceContext {
         element_by_uid: Default::default(),
+        element_by_uid_cleanup_timer: Default::default(),
+        has_weak_ref: Reflect::has(&win, &JsValue::from_str("WeakRef")).unwrap_or_default(),
         last_container_bounding_rect_cache_id: 0,
         container_bounding_rect_cache_timer: None,
-        fake_weak_refs: Default::default(),
-        fake_weak_refs_timer: None,
-        fake_weak_refs_started: false,
     });
     let key = uuid::Uuid::new_v4().to_string();
     tabster_instance_context
         .write()
         .unwrap_throw()
-        .insert(String::new(), ctx.clone());
+        .insert(key.clone(), ctx.clone());
     web_sys::js_sys::Reflect::set(
         &win,
         &web_sys::wasm_bindgen::JsValue::from_str("__tabsterInstanceContext"),
@@ -676,6 +1018,41 @@ pub fn get_instance_context(get_window: &Arc<GetWindow>) -> Arc<InstanceContext>
     ctx
 }
 
+/// Drops the context of the window along with its held elements and timers,
+/// a new one is created by the next `get_instance_context()` call.
+pub fn dispose_instance_context(get_window: &Arc<GetWindow>) {
+    let win = get_window();
+    let Some(key) = win
+        .get("__tabsterInstanceContext")
+        .and_then(|obj| obj.as_string())
+    else {
+        return;
+    };
+
+    let ctx = TABSTER_INSTANCE_CONTEXT
+        .get_or_init(Default::default)
+        .write()
+        .unwrap_throw()
+        .remove(&key);
+
+    if let Some(ctx) = ctx {
+        if let Some(timer) = ctx
+            .element_by_uid_cleanup_timer
+            .write()
+            .unwrap_throw()
+            .take()
+        {
+            win.clear_timeout_with_handle(timer);
+        }
+        if let Some(timer) = ctx.container_bounding_rect_cache_timer {
+            win.clear_timeout_with_handle(timer);
+        }
+        ctx.element_by_uid.write().unwrap_throw().clear();
+    }
+
+    Reflect::delete_property(&win, &JsValue::from_str("__tabsterInstanceContext")).unwrap_throw();
+}
+
 // pub fn  create_weak_map<K extends object, V>(win: Window) -> WeakMap {
 //     const ctx = (win as WindowWithUtilsConext).__tabsterInstanceContext;
 //     return new (ctx?.basics.WeakMap || WeakMap)();
@@ -797,6 +1174,34 @@ pub fn get_radio_button_group(element: &Element) -> Option<types::RadioButtonGro
     })
 }
 
+/// Returns the closest `uncontrolled: { completely: true }` area the element is in.
+/// Tabster doesn't handle Tab inside such an area at all.
+pub fn get_uncontrolled_completely_container(
+    tabster: &Arc<RefCell<TabsterCore>>,
+    element: &HtmlElement,
+) -> Option<HtmlElement> {
+    let mut el = Some(element.clone());
+
+    while let Some(el_ref) = el {
+        let completely =
+            get_tabster_on_element(tabster, &el_ref).is_some_and(|tabster_on_element| {
+                tabster_on_element
+                    .borrow()
+                    .uncontrolled
+                    .as_ref()
+                    .is_some_and(|uncontrolled| uncontrolled.completely.unwrap_or_default())
+            });
+
+        if completely {
+            return Some(el_ref);
+        }
+
+        el = DOM::get_parent_element(Some(el_ref));
+    }
+
+    None
+}
+
 /// If the passed element is Tabster dummy input, returns the container element this dummy input belongs to.
 /// element: Element to check for being dummy input.
 /// returns: Dummy input container element (if the passed element is a dummy input) or null.
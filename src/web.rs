@@ -1,8 +1,26 @@
+use serde::Serialize;
 use web_sys::{
     wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt},
-    EventTarget, Node, Window,
+    CustomEvent, CustomEventInit, EventTarget, Node, Window,
 };
 
+/// Dispatches a bubbling and composed `CustomEvent`, returns `false` when it
+/// is canceled.
+pub fn dispatch_custom_event(
+    target: &EventTarget,
+    event_name: &str,
+    detail: &impl Serialize,
+) -> bool {
+    let init = CustomEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    init.set_composed(true);
+    init.set_detail(&serde_wasm_bindgen::to_value(detail).unwrap_throw());
+    let event = CustomEvent::new_with_event_init_dict(event_name, &init).unwrap_throw();
+
+    target.dispatch_event(&event).unwrap_throw()
+}
+
 pub fn set_timeout(window: &Window, handler: impl Fn() + 'static, timeout: i32) -> i32 {
     let handler = Box::new(handler) as Box<dyn Fn() + 'static>;
     let handler = Closure::once_into_js(handler);
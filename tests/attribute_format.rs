@@ -0,0 +1,119 @@
+//! `data-tabster` values as written by the upstream JS tabster, they must be
+//! read and written back byte to byte.
+
+use tabster::{
+    get_tabster_attribute,
+    types::{RootProps, TabsterAttributeProps},
+    TABSTER_ATTRIBUTE_NAME,
+};
+
+const UPSTREAM_ATTRIBUTES: &[&str] = &[
+    r#"{"root":{}}"#,
+    r#"{"root":{"restoreFocusOrder":2}}"#,
+    r#"{"deloser":{}}"#,
+    r#"{"deloser":{"restoreFocusOrder":1,"noSelectorCheck":true}}"#,
+    r#"{"uncontrolled":{}}"#,
+    r#"{"uncontrolled":{"completely":true}}"#,
+    r#"{"modalizer":{"id":"dialog-1"}}"#,
+    r#"{"modalizer":{"id":"m","isOthersAccessible":true,"isAlwaysAccessible":false,"isNoFocusFirst":true,"isNoFocusDefault":true,"isTrapped":true}}"#,
+    r#"{"focusable":{"isDefault":true}}"#,
+    r#"{"focusable":{"ignoreAriaDisabled":true,"excludeFromMover":true}}"#,
+    r#"{"focusable":{"ignoreKeydown":{"Tab":true,"Escape":true,"Enter":true,"ArrowUp":true,"ArrowDown":true,"ArrowLeft":true,"ArrowRight":true,"PageUp":true,"PageDown":true,"Home":true,"End":true}}}"#,
+    r#"{"groupper":{}}"#,
+    r#"{"groupper":{"tabbability":2}}"#,
+    r#"{"groupper":{"tabbability":1,"delegated":true}}"#,
+    r#"{"mover":{}}"#,
+    r#"{"mover":{"direction":1}}"#,
+    r#"{"mover":{"direction":3,"memorizeCurrent":true,"tabbable":true,"cyclic":true}}"#,
+    r#"{"mover":{"direction":4,"trackState":true,"visibilityAware":2,"hasDefault":false,"visibilityTolerance":0.8}}"#,
+    r#"{"observed":{"names":["search-input"]}}"#,
+    r#"{"observed":{"names":["panel","panel-primary"],"details":{"kind":"panel"}}}"#,
+    r#"{"sys":{}}"#,
+    r#"{"sys":{"dummyInputsPosition":2}}"#,
+    r#"{"restorer":{"type":0}}"#,
+    r#"{"restorer":{"type":1}}"#,
+    r#"{"deloser":{},"root":{}}"#,
+    r#"{"root":{},"sys":{"dummyInputsPosition":1}}"#,
+    r#"{"modalizer":{"id":"dialog"},"groupper":{"tabbability":2}}"#,
+    r#"{"focusable":{"isDefault":true},"mover":{"cyclic":true}}"#,
+    r#"{"mover":{"direction":0},"sys":{"dummyInputsPosition":0}}"#,
+    r#"{"focusable":{"isDefault":true},"observed":{"names":["primary"]}}"#,
+    // Unmodified output of the upstream tabster and Fluent UI hooks, the key
+    // order is the one of the JS objects.
+    r#"{"mover":{"cyclic":false,"direction":1,"memorizeCurrent":true}}"#,
+    r#"{"mover":{"cyclic":true,"direction":3,"memorizeCurrent":true,"tabbable":true,"hasDefault":false}}"#,
+    r#"{"mover":{"direction":1,"visibilityTolerance":1}}"#,
+    r#"{"mover":{"direction":0,"visibilityAware":1,"visibilityTolerance":0.5}}"#,
+    r#"{"groupper":{"tabbability":2},"focusable":{}}"#,
+    r#"{"groupper":{"tabbability":2},"focusable":{"ignoreKeydown":{"Enter":true}}}"#,
+    r#"{"groupper":{"tabbability":2},"modalizer":{"id":"dialog"}}"#,
+    r#"{"modalizer":{"id":"fui-1","isOthersAccessible":true,"isAlwaysAccessible":false,"isTrapped":false}}"#,
+    r#"{"restorer":{"type":0},"modalizer":{"id":"fui-2"}}"#,
+    r#"{"root":{},"deloser":{}}"#,
+    r#"{"sys":{"dummyInputsPosition":1},"root":{}}"#,
+    // Keys unknown to the typed props and explicit nulls.
+    r#"{"root":{},"outline":{"isIgnored":true}}"#,
+    r#"{"mover":{"cyclic":null,"direction":1}}"#,
+    r#"{"focusable":{"isDefault":null},"groupper":null}"#,
+];
+
+const INVALID_ATTRIBUTES: &[&str] = &[
+    r#"{"root":{"restoreFocusOrder":5}}"#,
+    r#"{"deloser":{"restoreFocusOrder":5}}"#,
+    r#"{"groupper":{"tabbability":3}}"#,
+    r#"{"mover":{"direction":5}}"#,
+    r#"{"mover":{"visibilityAware":3}}"#,
+    r#"{"sys":{"dummyInputsPosition":3}}"#,
+    r#"{"restorer":{"type":2}}"#,
+    r#"{"restorer":{}}"#,
+    r#"{"modalizer":{}}"#,
+];
+
+#[test]
+fn upstream_attributes_round_trip() {
+    for attribute in UPSTREAM_ATTRIBUTES {
+        let props: TabsterAttributeProps = serde_json::from_str(attribute)
+            .unwrap_or_else(|err| panic!("{attribute} is not parsed: {err}"));
+
+        assert_eq!(
+            get_tabster_attribute(props),
+            (TABSTER_ATTRIBUTE_NAME.to_string(), attribute.to_string())
+        );
+    }
+}
+
+#[test]
+fn modified_attributes_keep_the_source_format() {
+    let mut props: TabsterAttributeProps =
+        serde_json::from_str(r#"{"mover":{"visibilityTolerance":1,"cyclic":false},"outline":{}}"#)
+            .unwrap();
+    props.mover.as_mut().unwrap().cyclic = Some(true);
+    props.root = Some(RootProps {
+        restore_focus_order: None,
+    });
+
+    assert_eq!(
+        get_tabster_attribute(props).1,
+        r#"{"mover":{"visibilityTolerance":1,"cyclic":true},"outline":{},"root":{}}"#
+    );
+
+    let mut props: TabsterAttributeProps =
+        serde_json::from_str(r#"{"groupper":{},"mover":{"direction":1}}"#).unwrap();
+    props.groupper = None;
+    props.mover.as_mut().unwrap().visibility_tolerance = Some(0.8);
+
+    assert_eq!(
+        get_tabster_attribute(props).1,
+        r#"{"mover":{"direction":1,"visibilityTolerance":0.8}}"#
+    );
+}
+
+#[test]
+fn invalid_attributes_are_rejected() {
+    for attribute in INVALID_ATTRIBUTES {
+        assert!(
+            serde_json::from_str::<TabsterAttributeProps>(attribute).is_err(),
+            "{attribute} is parsed"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use tabster::{
+    types::{DOMBackend, TabsterDOMRect},
+    MemoryDOM,
+};
+
+#[test]
+fn natively_focusable_elements() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let focusable = [
+        dom.append_element(&body, "button", &[]),
+        dom.append_element(&body, "input", &[]),
+        dom.append_element(&body, "a", &[("href", "#")]),
+        dom.append_element(&body, "div", &[("tabindex", "0")]),
+        dom.append_element(&body, "div", &[("contenteditable", "true")]),
+        dom.append_element(&body, "video", &[("controls", "")]),
+    ];
+    let not_focusable = [
+        dom.append_element(&body, "button", &[("disabled", "")]),
+        dom.append_element(&body, "a", &[]),
+        dom.append_element(&body, "div", &[]),
+        dom.append_element(&body, "video", &[]),
+    ];
+
+    for el in focusable {
+        assert!(dom.is_focusable(&el, None, None, None));
+    }
+    for el in not_focusable {
+        assert!(!dom.is_focusable(&el, None, None, None));
+    }
+
+    let details = dom.append_element(&body, "details", &[]);
+    let summary = dom.append_element(&details, "summary", &[]);
+    let stray_summary = dom.append_element(&body, "summary", &[]);
+
+    assert!(dom.is_focusable(&summary, None, None, None));
+    assert!(!dom.is_focusable(&stray_summary, None, None, None));
+}
+
+#[test]
+fn programmatically_focusable() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let el = dom.append_element(&body, "div", &[("tabindex", "-1")]);
+
+    assert!(!dom.is_focusable(&el, None, None, None));
+    assert!(dom.is_focusable(&el, Some(true), None, None));
+}
+
+#[test]
+fn hidden_and_disabled_ancestors() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let hidden = dom.append_element(&body, "div", &[("aria-hidden", "true")]);
+    let in_hidden = dom.append_element(&hidden, "button", &[]);
+    let fieldset = dom.append_element(&body, "fieldset", &[("disabled", "")]);
+    let in_disabled = dom.append_element(&fieldset, "button", &[]);
+
+    assert!(!dom.is_focusable(&in_hidden, None, None, None));
+    assert!(!dom.is_focusable(&in_disabled, None, None, None));
+    assert!(dom.is_focusable(&in_hidden, None, None, Some(true)));
+
+    dom.set_attribute(&hidden, "aria-hidden", "false");
+    assert!(dom.is_accessible(&in_hidden));
+}
+
+#[test]
+fn not_rendered_elements() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let container = dom.append_element(&body, "div", &[]);
+    let button = dom.append_element(&container, "button", &[]);
+
+    dom.set_display_none(&container, true);
+    assert!(!dom.is_focusable(&button, None, None, None));
+    assert!(dom.is_focusable(&button, None, Some(true), None));
+
+    dom.set_display_none(&container, false);
+    assert!(dom.is_visible(&button));
+
+    // This might happen, for example, if the <body> is in hidden <iframe>.
+    dom.set_bounding_client_rect(&body, TabsterDOMRect::default());
+    assert!(!dom.is_visible(&button));
+}
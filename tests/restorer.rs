@@ -0,0 +1,73 @@
+//! Run in a browser with `wasm-pack test --headless --firefox`.
+#![cfg(target_arch = "wasm32")]
+
+use tabster::{create_tabster, get_restorer, types::TabsterCoreProps, TABSTER_ATTRIBUTE_NAME};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{
+    js_sys::Promise,
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    HtmlElement, Window,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Lets the mutation observer and the timers of Tabster run.
+async fn wait(win: &Window) {
+    for _ in 0..3 {
+        let promise = Promise::new(&mut |resolve, _| {
+            win.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0)
+                .unwrap_throw();
+        });
+        JsFuture::from(promise).await.unwrap_throw();
+    }
+}
+
+fn create_element(win: &Window, tag_name: &str, attribute: &str) -> HtmlElement {
+    let doc = win.document().unwrap_throw();
+    let element: HtmlElement = doc.create_element(tag_name).unwrap_throw().unchecked_into();
+    element
+        .set_attribute(TABSTER_ATTRIBUTE_NAME, attribute)
+        .unwrap_throw();
+
+    element
+}
+
+#[wasm_bindgen_test]
+async fn closing_the_popup_focuses_the_trigger() {
+    let win = web_sys::window().unwrap_throw();
+    let doc = win.document().unwrap_throw();
+    let body = doc.body().unwrap_throw();
+    let tabster = create_tabster(
+        win.clone(),
+        TabsterCoreProps {
+            auto_root: None,
+            control_tab: None,
+            get_parent: None,
+        },
+    );
+    get_restorer(&tabster);
+    tabster.keyboard_navigation().set_val(true);
+    wait(&win).await;
+
+    let trigger = create_element(&win, "button", r#"{"restorer":{"type":1}}"#);
+    body.append_child(&trigger).unwrap_throw();
+    wait(&win).await;
+    trigger.focus().unwrap_throw();
+    wait(&win).await;
+
+    let popup = create_element(&win, "div", r#"{"restorer":{"type":0}}"#);
+    let popup_button: HtmlElement = doc.create_element("button").unwrap_throw().unchecked_into();
+    popup.append_child(&popup_button).unwrap_throw();
+    body.append_child(&popup).unwrap_throw();
+    wait(&win).await;
+    popup_button.focus().unwrap_throw();
+    wait(&win).await;
+
+    popup.remove();
+    wait(&win).await;
+
+    assert_eq!(doc.active_element(), Some(trigger.into()));
+
+    tabster.dispose();
+}
@@ -0,0 +1,80 @@
+//! The element storage must not keep anything once the elements are gone,
+//! run in a browser with `wasm-pack test --headless --firefox`.
+#![cfg(target_arch = "wasm32")]
+
+use tabster::{
+    create_tabster, get_groupper, get_mover, types::TabsterCoreProps, Tabster,
+    TABSTER_ATTRIBUTE_NAME,
+};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{
+    js_sys::Promise,
+    wasm_bindgen::{JsCast, UnwrapThrowExt},
+    HtmlElement, Window,
+};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Lets the mutation observer and the timers of Tabster run.
+async fn wait(win: &Window) {
+    for _ in 0..3 {
+        let promise = Promise::new(&mut |resolve, _| {
+            win.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0)
+                .unwrap_throw();
+        });
+        JsFuture::from(promise).await.unwrap_throw();
+    }
+}
+
+fn create_element(win: &Window, attribute: &str) -> HtmlElement {
+    let doc = win.document().unwrap_throw();
+    let element: HtmlElement = doc.create_element("div").unwrap_throw().unchecked_into();
+    element
+        .set_attribute(TABSTER_ATTRIBUTE_NAME, attribute)
+        .unwrap_throw();
+    element.set_inner_html("<button>One</button><button>Two</button>");
+
+    element
+}
+
+fn storage_entries_count(tabster: &Tabster) -> usize {
+    tabster.core.borrow().storage_entries_count()
+}
+
+#[wasm_bindgen_test]
+async fn entries_are_dropped_with_their_elements() {
+    let win = web_sys::window().unwrap_throw();
+    let body = win.document().unwrap_throw().body().unwrap_throw();
+    let tabster = create_tabster(
+        win.clone(),
+        TabsterCoreProps {
+            auto_root: None,
+            control_tab: None,
+            get_parent: None,
+        },
+    );
+    get_groupper(&tabster);
+    get_mover(&tabster);
+    wait(&win).await;
+
+    let initial_count = storage_entries_count(&tabster);
+
+    let groupper = create_element(&win, r#"{"groupper":{"tabbability":2}}"#);
+    let mover = create_element(&win, r#"{"mover":{"trackState":true}}"#);
+    body.append_child(&groupper).unwrap_throw();
+    body.append_child(&mover).unwrap_throw();
+    wait(&win).await;
+
+    assert_eq!(storage_entries_count(&tabster), initial_count + 2);
+
+    groupper.remove();
+    mover.remove();
+    wait(&win).await;
+
+    assert_eq!(storage_entries_count(&tabster), initial_count);
+
+    tabster.dispose();
+
+    assert_eq!(storage_entries_count(&tabster), 0);
+}
@@ -0,0 +1,129 @@
+use tabster::{
+    create_element_tree_walker, get_last_child, types::DOMBackend, FilterResult, MemoryDOM,
+    MemoryElement,
+};
+
+/// What the `FocusableAPI` search does with an element outside of the Tabster
+/// parts: the tabbable ones are accepted, the inaccessible subtrees rejected.
+fn accept_element(dom: &MemoryDOM, el: &MemoryElement) -> FilterResult {
+    if !dom.is_accessible(el) {
+        FilterResult::Reject
+    } else if dom.is_focusable(el, None, None, None) {
+        FilterResult::Accept
+    } else {
+        FilterResult::Skip
+    }
+}
+
+/// Moves the focus to the next (or the previous) tabbable element of the body.
+fn press_tab(dom: &MemoryDOM, is_backward: bool) -> bool {
+    let body = dom.body().unwrap();
+    let active_element = dom.get_active_element().unwrap();
+    let mut walker = create_element_tree_walker(dom, &body, |el| accept_element(dom, el));
+
+    let next = if active_element != body {
+        walker.set_current_node(&active_element);
+
+        if is_backward {
+            walker.previous_node()
+        } else {
+            walker.next_node()
+        }
+    } else if is_backward {
+        let Some(last_child) = get_last_child(dom, &body) else {
+            return false;
+        };
+
+        if accept_element(dom, &last_child) == FilterResult::Accept {
+            Some(last_child)
+        } else {
+            walker.set_current_node(&last_child);
+            walker.previous_node()
+        }
+    } else {
+        walker.next_node()
+    };
+
+    // The walker visits the root as well.
+    let Some(next) = next.filter(|next| *next != body) else {
+        return false;
+    };
+
+    dom.focus(&next);
+    true
+}
+
+/// Tabs through the whole body, returns the focused elements in order.
+fn tab_through(dom: &MemoryDOM, is_backward: bool) -> Vec<MemoryElement> {
+    let mut focused = vec![];
+
+    while press_tab(dom, is_backward) {
+        focused.push(dom.get_active_element().unwrap());
+    }
+
+    focused
+}
+
+#[test]
+fn follows_document_order() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let first = dom.append_element(&body, "button", &[]);
+    let list = dom.append_element(&body, "ul", &[]);
+    let item = dom.append_element(&list, "li", &[]);
+    let link = dom.append_element(&item, "a", &[("href", "#")]);
+    let div = dom.append_element(&item, "div", &[("tabindex", "0")]);
+    let last = dom.append_element(&body, "input", &[]);
+
+    assert_eq!(tab_through(&dom, false), vec![first, link, div, last]);
+    assert_eq!(tab_through(&dom, true), vec![div, link, first]);
+}
+
+#[test]
+fn starts_from_the_end_backwards() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let first = dom.append_element(&body, "button", &[]);
+    let container = dom.append_element(&body, "div", &[]);
+    let last = dom.append_element(&container, "button", &[]);
+    dom.append_element(&container, "span", &[]);
+
+    assert_eq!(tab_through(&dom, true), vec![last, first]);
+}
+
+#[test]
+fn skips_what_is_not_tabbable() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let button = dom.append_element(&body, "button", &[]);
+    dom.append_element(&body, "button", &[("disabled", "")]);
+    dom.append_element(&body, "button", &[("tabindex", "-1")]);
+    dom.append_element(&body, "a", &[]);
+    dom.append_element(&body, "div", &[]);
+
+    let hidden = dom.append_element(&body, "div", &[("aria-hidden", "true")]);
+    dom.append_element(&hidden, "button", &[]);
+
+    let not_displayed = dom.append_element(&body, "div", &[]);
+    dom.append_element(&not_displayed, "button", &[]);
+    dom.set_display_none(&not_displayed, true);
+
+    let details = dom.append_element(&body, "details", &[]);
+    let summary = dom.append_element(&details, "summary", &[]);
+
+    assert_eq!(tab_through(&dom, false), vec![button, summary]);
+}
+
+#[test]
+fn focuses_only_focusable_elements() {
+    let dom = MemoryDOM::new();
+    let body = dom.body().unwrap();
+    let div = dom.append_element(&body, "div", &[]);
+    let programmatic = dom.append_element(&body, "div", &[("tabindex", "-1")]);
+
+    dom.focus(&div);
+    assert_eq!(dom.get_active_element(), Some(body));
+
+    dom.focus(&programmatic);
+    assert_eq!(dom.get_active_element(), Some(programmatic));
+}